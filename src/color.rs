@@ -0,0 +1,150 @@
+use crossterm::style::Color;
+
+// Colors //
+
+/// Parse a color name, such as `red` or `dark_blue`, a hex color such as `#ff8800`, or an ANSI
+/// color number from 0 to 255
+pub fn parse(name: &str) -> Option<Color> {
+    if let Ok(value) = name.parse::<u8>() {
+        return Some(Color::AnsiValue(value));
+    }
+
+    if let Some(hex) = name.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb { r: channel(0)?, g: channel(2)?, b: channel(4)? });
+    }
+
+    Color::try_from(name).ok()
+}
+
+/// The named color of one of the 16 standard ANSI colors
+pub fn ansi16(index: u8) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::DarkRed,
+        2 => Color::DarkGreen,
+        3 => Color::DarkYellow,
+        4 => Color::DarkBlue,
+        5 => Color::DarkMagenta,
+        6 => Color::DarkCyan,
+        7 => Color::Grey,
+        8 => Color::DarkGrey,
+        9 => Color::Red,
+        10 => Color::Green,
+        11 => Color::Yellow,
+        12 => Color::Blue,
+        13 => Color::Magenta,
+        14 => Color::Cyan,
+        15 => Color::White,
+        index => Color::AnsiValue(index)
+    }
+}
+
+/// The escape sequence that sets the foreground or background color
+///
+/// The 16 standard colors use their own codes, 30 to 37 and 90 to 97 or 40 to 47 and 100 to 107,
+/// which unlike crossterm's `38;5;N` work in terminals without the 256 color palette.
+pub fn sgr(color: Color, background: bool) -> String {
+    let index = match color {
+        Color::Reset => return format!("\x1b[{}m", if background { 49 } else { 39 }),
+        Color::Rgb { r, g, b } => return format!("\x1b[{};2;{};{};{}m", if background { 48 } else { 38 }, r, g, b),
+        Color::AnsiValue(index) if index >= 16 => return format!("\x1b[{};5;{}m", if background { 48 } else { 38 }, index),
+        Color::AnsiValue(index) => index,
+        Color::Black => 0,
+        Color::DarkRed => 1,
+        Color::DarkGreen => 2,
+        Color::DarkYellow => 3,
+        Color::DarkBlue => 4,
+        Color::DarkMagenta => 5,
+        Color::DarkCyan => 6,
+        Color::Grey => 7,
+        Color::DarkGrey => 8,
+        Color::Red => 9,
+        Color::Green => 10,
+        Color::Yellow => 11,
+        Color::Blue => 12,
+        Color::Magenta => 13,
+        Color::Cyan => 14,
+        Color::White => 15
+    };
+
+    let base = match (background, index < 8) {
+        (false, true) => 30,
+        (false, false) => 90 - 8,
+        (true, true) => 40,
+        (true, false) => 100 - 8
+    };
+    format!("\x1b[{}m", base + index as u32)
+}
+
+/// Text in a foreground color
+pub fn paint(text: &str, color: Color) -> String {
+    format!("{}{}{}", sgr(color, false), text, sgr(Color::Reset, false))
+}
+
+/// Color of each of the 16 standard ANSI colors, as most terminals show them
+const ANSI_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0), (128, 0, 0), (0, 128, 0), (128, 128, 0), (0, 0, 128), (128, 0, 128), (0, 128, 128), (192, 192, 192),
+    (128, 128, 128), (255, 0, 0), (0, 255, 0), (255, 255, 0), (0, 0, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255)
+];
+
+// Squared distance between two colors
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    channel(r1, r2) + channel(g1, g2) + channel(b1, b2)
+}
+
+/// The closest of the 16 standard ANSI colors
+pub fn to_ansi16(rgb: (u8, u8, u8)) -> u8 {
+    (0..16).min_by_key(|&index| distance(rgb, ANSI_16[index])).unwrap_or(0) as u8
+}
+
+/// The closest color of the 256 color palette, from its 6x6x6 color cube and gray ramp
+pub fn to_ansi256(rgb: (u8, u8, u8)) -> u8 {
+    // Levels of the color cube's channels
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |value: u8| (0..6).min_by_key(|&index| (LEVELS[index] as i32 - value as i32).abs()).unwrap_or(0);
+
+    let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube = (16 + 36 * r + 6 * g + b) as u8;
+    let cube_rgb = (LEVELS[r], LEVELS[g], LEVELS[b]);
+
+    // The gray ramp goes from 8 to 238 in steps of 10
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray_index = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + gray_index * 10;
+
+    if distance(rgb, (gray, gray, gray)) < distance(rgb, cube_rgb) {
+        232 + gray_index
+    } else {
+        cube
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sgr_codes() {
+        assert_eq!(sgr(Color::DarkRed, false), "\x1b[31m");
+        assert_eq!(sgr(Color::Cyan, false), "\x1b[96m");
+        assert_eq!(sgr(Color::AnsiValue(7), true), "\x1b[47m");
+        assert_eq!(sgr(Color::AnsiValue(15), true), "\x1b[107m");
+        assert_eq!(sgr(Color::AnsiValue(16), false), "\x1b[38;5;16m");
+        assert_eq!(sgr(Color::Rgb { r: 1, g: 2, b: 3 }, true), "\x1b[48;2;1;2;3m");
+        assert_eq!(sgr(Color::Reset, true), "\x1b[49m");
+    }
+
+    #[test]
+    fn ansi16_names() {
+        for index in 0..16 {
+            assert_ne!(ansi16(index), Color::AnsiValue(index));
+            assert_eq!(sgr(ansi16(index), false), sgr(Color::AnsiValue(index), false));
+        }
+        assert_eq!(ansi16(to_ansi16((250, 10, 10))), Color::Red);
+    }
+}
@@ -0,0 +1,876 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+
+use crate::color;
+use crate::graphics::Protocol;
+use crate::logo;
+use crate::modules::{self, Module};
+use crate::raster::{self, ColorDepth};
+use crate::render::LogoPosition;
+use crate::snapshot::Palette;
+use crate::template::Template;
+
+// Errors //
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read
+    Io(PathBuf, std::io::Error),
+    /// The file is not valid TOML, or does not match the expected structure
+    Parse(PathBuf, toml::de::Error),
+    /// The file was loaded, but a setting in it is not valid, such as an unknown key or module
+    Invalid {
+        path: PathBuf,
+        /// Line and column of the setting, starting from 1
+        position: Option<(usize, usize)>,
+        message: String
+    },
+    /// An `OXIFETCH_*` environment variable has a value that is not valid for its setting
+    Env(String, String)
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            ConfigError::Parse(path, e) => {
+                // Move the position given at the end of the message to after the path
+                let message = e.to_string();
+                match e.line_col() {
+                    Some((line, column)) => {
+                        let message = message.rsplit_once(" at line ").map_or(message.as_str(), |(message, _)| message);
+                        write!(f, "{}:{}:{}: {}", path.display(), line + 1, column + 1, message)
+                    },
+                    None => write!(f, "{}: {}", path.display(), message)
+                }
+            },
+            ConfigError::Invalid { path, position: Some((line, column)), message } => write!(f, "{}:{}:{}: {}", path.display(), line, column, message),
+            ConfigError::Invalid { path, position: None, message } => write!(f, "{}: {}", path.display(), message),
+            ConfigError::Env(variable, message) => write!(f, "${}: {}", variable, message)
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Read and deserialize a TOML file
+pub(crate) fn load_toml<T: DeserializeOwned>(path: &Path) -> Result<T, ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+    toml::from_str(&contents).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))
+}
+
+// Read the settings of a config file on top of the files it includes, checking each of them
+//
+// `including` is the chain of files that led to this one, to catch files that include themselves.
+fn read_settings(path: &Path, including: &mut Vec<PathBuf>, problems: &mut Vec<ConfigError>) -> Result<toml::value::Table, ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+    let invalid = |position, message| ConfigError::Invalid { path: path.to_path_buf(), position, message };
+
+    // Leave out the settings whose values have the wrong type, so the rest of the file still applies
+    let mut own: toml::value::Table = toml::from_str(&contents).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))?;
+    remove_invalid(&mut own, None, &mut |key, profile, e| {
+        // Leave out the key that toml adds at the end of the message
+        let message = e.to_string();
+        let message = message.rsplit_once(" for key `").map_or(message.as_str(), |(message, _)| message);
+        let (position, message) = match profile {
+            Some(profile) => (
+                profile_key_position(&contents, profile, key).or_else(|| key_position(&contents, key)),
+                format!("Invalid value for `{}` in profile {}: {}", key, profile, message)
+            ),
+            None => (
+                key_position(&contents, key.rsplit('.').next().unwrap_or(key)),
+                format!("Invalid value for `{}`: {}", key, message)
+            )
+        };
+        problems.push(invalid(position, message));
+    });
+
+    // Deserialize the config, keeping track of the keys that are not used
+    let mut unknown = Vec::new();
+    let config: Config = serde_ignored::deserialize(toml::Value::Table(own.clone()), |key| {
+        // Leave out the `?` that stands for an optional value
+        unknown.push(key.to_string().split('.').filter(|part| *part != "?").collect::<Vec<_>>().join("."));
+    }).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))?;
+
+    problems.extend(unknown.into_iter().map(|key| {
+        let name = key.rsplit('.').next().unwrap_or(&key);
+        invalid(key_position(&contents, name), format!("Unknown key `{}`", key))
+    }));
+
+    // Settings that only apply at the top level or only in a profile
+    if !config.hosts.is_empty() {
+        problems.push(invalid(key_position(&contents, "hosts"), "`hosts` can only be set in a profile".to_string()));
+    }
+    for (name, profile) in &config.profiles {
+        for (key, set) in [("include", !profile.include.is_empty()), ("profiles", !profile.profiles.is_empty())] {
+            if set {
+                problems.push(invalid(key_position(&contents, key), format!("`{}` cannot be set in profile {}", key, name)));
+            }
+        }
+    }
+
+    for name in config.logo_colors.iter().filter(|name| logo::palette_color(name).is_none()) {
+        problems.push(invalid(string_position(&contents, name), format!("Unknown color `{}` in logo_colors", name)));
+    }
+
+    // Check the entries of the modules lists
+    let lists = std::iter::once(&config.modules).chain(config.profiles.values().map(|profile| &profile.modules));
+    for options in lists.flatten().flatten() {
+        if let Err(e) = options.check() {
+            problems.push(invalid(string_position(&contents, &options.name), e));
+        }
+    }
+
+    own.remove("include");
+
+    // Read the included files, where later files take precedence over earlier ones
+    let mut settings = toml::value::Table::new();
+    including.push(std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()));
+    for name in &config.include {
+        let include = path.parent().unwrap_or(Path::new("")).join(name);
+        if including.contains(&std::fs::canonicalize(&include).unwrap_or_else(|_| include.clone())) {
+            problems.push(invalid(string_position(&contents, &name.to_string_lossy()), format!("{} includes itself", include.display())));
+            continue;
+        }
+        match read_settings(&include, including, problems) {
+            Ok(included) => merge(&mut settings, included),
+            Err(e) => problems.push(e)
+        }
+    }
+    including.pop();
+
+    merge(&mut settings, own);
+    Ok(settings)
+}
+
+// Remove the settings that cannot be deserialized on their own, the settings in each profile first,
+// and report them with the profile they are in
+fn remove_invalid(table: &mut toml::value::Table, profile: Option<&str>, report: &mut dyn FnMut(&str, Option<&str>, toml::de::Error)) {
+    if profile.is_none() {
+        if let Some(toml::Value::Table(profiles)) = table.get_mut("profiles") {
+            let names: Vec<String> = profiles.keys().cloned().collect();
+            for name in names {
+                match profiles.get_mut(&name) {
+                    Some(toml::Value::Table(profile)) => remove_invalid(profile, Some(&name), report),
+                    // Profiles that are not tables
+                    _ => if let Some(Err(e)) = profiles.get(&name).map(|profile| profile.clone().try_into::<Config>()) {
+                        report(&format!("profiles.{}", name), None, e);
+                        profiles.remove(&name);
+                    }
+                }
+            }
+        }
+    }
+
+    let keys: Vec<String> = table.keys().cloned().collect();
+    for key in keys {
+        let mut single = toml::value::Table::new();
+        single.insert(key.clone(), table[&key].clone());
+        if let Err(e) = toml::Value::Table(single).try_into::<Config>() {
+            report(&key, profile, e);
+            table.remove(&key);
+        }
+    }
+}
+
+// Set the settings of `overlay` in `base`, merging tables that are in both
+fn merge(base: &mut toml::value::Table, overlay: toml::value::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+// Whether a hostname matches a pattern, where `*` matches any characters
+fn host_matches(pattern: &str, hostname: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let hostname = hostname.to_lowercase();
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let mut rest = match hostname.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false
+    };
+
+    // Without a `*` the whole hostname has to match
+    let parts: Vec<&str> = parts.collect();
+    let (last, middle) = match parts.split_last() {
+        Some(split) => split,
+        None => return rest.is_empty()
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+// Interpret the value of an environment variable as a boolean
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" => Some(false),
+        _ => None
+    }
+}
+
+// Line and column of the first line that the matcher finds a byte offset in, skipping comments
+fn find_position(source: &str, matcher: impl Fn(&str) -> Option<usize>) -> Option<(usize, usize)> {
+    source.lines().enumerate()
+        .filter(|(_, text)| !text.trim_start().starts_with('#'))
+        .find_map(|(line, text)| matcher(text).map(|start| (line + 1, text[..start].chars().count() + 1)))
+}
+
+// Position of a key being set, either bare or quoted
+fn key_position(source: &str, key: &str) -> Option<(usize, usize)> {
+    find_position(source, |text| {
+        text.match_indices(key).map(|(start, _)| start).find(|&start| {
+            let before = text[..start].chars().next_back();
+            let after = text[start + key.len()..].trim_start_matches(['"', '\'']).trim_start();
+            before.is_none_or(|c| !(c.is_alphanumeric() || c == '_' || c == '-')) && after.starts_with('=')
+        })
+    })
+}
+
+// Position of a key being set in a profile, searched for from the profile's table header
+fn profile_key_position(source: &str, profile: &str, key: &str) -> Option<(usize, usize)> {
+    let headers = [format!("[profiles.{}]", profile), format!("[profiles.\"{}\"]", profile)];
+    let start = source.lines().position(|text| headers.iter().any(|header| text.trim() == header))?;
+    let section = source.lines().skip(start).collect::<Vec<_>>().join("\n");
+    key_position(&section, key).map(|(line, column)| (line + start, column))
+}
+
+// Position of a quoted string value
+fn string_position(source: &str, value: &str) -> Option<(usize, usize)> {
+    find_position(source, |text| {
+        text.find(&format!("\"{}\"", value)).or_else(|| text.find(&format!("'{}'", value)))
+    })
+}
+
+// Config Structs //
+
+/// Prefix of the environment variables that override settings
+pub const ENV_PREFIX: &str = "OXIFETCH_";
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// The image with the module lines beside it
+    #[default]
+    Text,
+    /// The collected information as a JSON document, see `oxifetch::output::Report`
+    Json,
+    /// The collected information as metrics in the Prometheus text format
+    Prometheus,
+    /// The collected information as a TOML document
+    Toml,
+    /// The collected information as a YAML document
+    Yaml,
+    /// The collected information as shell variable assignments
+    Env
+}
+
+/// When colors are printed
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// When printing to a terminal and `NO_COLOR` is not set, or when `CLICOLOR_FORCE` is set
+    #[default]
+    Auto,
+    Always,
+    Never
+}
+
+impl ColorMode {
+    /// Whether colors are printed
+    pub fn enabled(self) -> bool {
+        // Set to a value other than the ones that turn it off
+        let set = |name: &str, off: &[&str]| std::env::var_os(name).is_some_and(|value| !off.iter().any(|off| value == *off));
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto if set("NO_COLOR", &[""]) => false,
+            ColorMode::Auto if set("CLICOLOR_FORCE", &["", "0"]) => true,
+            ColorMode::Auto => std::io::stdout().is_terminal()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Name of the logo, see `oxifetch --list-logos`, where `auto` or no name selects the running
+    /// system's, or the path of a PNG or JPEG image
+    pub image_name: String,
+    /// Width of image files in columns, where 0 is worked out from the height
+    pub image_columns: u32,
+    /// Height of image files in rows, where 0 is worked out from the width
+    pub image_rows: u32,
+    /// Colors image files are printed with
+    pub image_colors: ColorDepth,
+    /// How image files are printed
+    pub image_protocol: Protocol,
+    /// Extra logo files and directories, which take precedence over the ones in the data directories
+    pub logo_paths: Vec<PathBuf>,
+    /// Colors replacing the logo's palette, used by its `${c1}` to `${c6}` markers
+    pub logo_colors: Vec<String>,
+    pub user: bool,
+    pub partition: bool,
+    pub os: bool,
+    pub computer_name: bool,
+    pub kernel_version: bool,
+    pub uptime: bool,
+    pub resolution: bool,
+    pub packages: bool,
+    pub theme: bool,
+    pub cpu_name: bool,
+    pub gpu_info: bool,
+    pub processes: bool,
+    pub ram: bool,
+    pub swap: bool,
+    pub disk_info: bool,
+    pub battery: bool,
+    pub locale: bool,
+    pub weather: bool,
+    pub weather_api_key: String,
+    pub colors: bool,
+    /// Number of colors shown by the colors module
+    pub palette_colors: Palette,
+    pub info_offset: usize,
+    /// Where the logo is printed relative to the information
+    pub logo_position: LogoPosition,
+    /// Columns of space before every line
+    pub logo_padding: usize,
+    /// Space between the logo and the information, in columns beside each other or lines above
+    /// each other
+    pub logo_gap: usize,
+    pub format: Format,
+    /// When colors are printed
+    pub color: ColorMode,
+    /// Refuse to run when the config has problems, instead of skipping the settings that are invalid
+    pub strict: bool,
+    /// The modules to show in order, replacing the module switches above when set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modules: Option<Vec<ModuleOptions>>,
+    /// Config files whose settings this file builds on, relative to this file
+    #[serde(deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<PathBuf>,
+    /// Named sets of settings that override the rest of the file when selected
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Config>,
+    /// Hostnames that select a profile when no profile is given, where `*` matches any characters
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<String>
+}
+
+// Whether a module is shown without a config file and in the generated config, where the weather
+// is only fetched once an API key is set and the palette is optional
+fn enabled_by_default(module: &str) -> bool {
+    !matches!(module, "weather" | "colors")
+}
+
+impl Default for Config {
+    /// The settings of a missing config file, which match the generated config
+    fn default() -> Config {
+        Config {
+            image_name: "auto".to_string(),
+            image_columns: raster::DEFAULT_COLUMNS,
+            image_rows: 0,
+            image_colors: ColorDepth::default(),
+            image_protocol: Protocol::default(),
+            logo_paths: Vec::new(),
+            logo_colors: Vec::new(),
+            user: enabled_by_default("user"),
+            partition: enabled_by_default("partition"),
+            os: enabled_by_default("os"),
+            computer_name: enabled_by_default("computer_name"),
+            kernel_version: enabled_by_default("kernel_version"),
+            uptime: enabled_by_default("uptime"),
+            resolution: enabled_by_default("resolution"),
+            packages: enabled_by_default("packages"),
+            theme: enabled_by_default("theme"),
+            cpu_name: enabled_by_default("cpu_name"),
+            gpu_info: enabled_by_default("gpu_info"),
+            processes: enabled_by_default("processes"),
+            ram: enabled_by_default("ram"),
+            swap: enabled_by_default("swap"),
+            disk_info: enabled_by_default("disk_info"),
+            battery: enabled_by_default("battery"),
+            locale: enabled_by_default("locale"),
+            weather: enabled_by_default("weather"),
+            weather_api_key: String::new(),
+            colors: enabled_by_default("colors"),
+            palette_colors: Palette::default(),
+            info_offset: 0,
+            logo_position: LogoPosition::default(),
+            logo_padding: 0,
+            // A column between the logo and the information, as the art ends at its widest line
+            logo_gap: 1,
+            format: Format::default(),
+            color: ColorMode::default(),
+            strict: false,
+            modules: None,
+            include: Vec::new(),
+            profiles: BTreeMap::new(),
+            hosts: Vec::new()
+        }
+    }
+}
+
+// Deserialize a single value or a list of them
+fn one_or_many<'de, D: serde::Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Vec<T>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>)
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values
+    })
+}
+
+/// An entry of the `modules` list, written as either a module name or a table
+// The derived implementations are inherent functions through `remote = "Self"`, so that the
+// trait implementations below can also accept a bare name
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(remote = "Self")]
+pub struct ModuleOptions {
+    /// Name or alias of the module
+    pub name: String,
+    /// Replaces the label printed before the module's values, and may use the module's placeholders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Layout of each line, where `{label}`, `{value}`, and the module's placeholders are replaced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Color of the label, either a name such as `dark_blue` or a hex color such as `#ff8800`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Skip lines whose information is unavailable instead of printing `Unknown`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hide_if_empty: bool
+}
+
+impl ModuleOptions {
+    pub fn new(name: impl Into<String>) -> ModuleOptions {
+        ModuleOptions { name: name.into(), ..ModuleOptions::default() }
+    }
+
+    /// Look up the module, checking that the options are valid for it
+    pub fn check(&self) -> Result<Box<dyn Module>, String> {
+        let module = modules::find(&self.name).ok_or_else(|| format!("Unknown module: {}", self.name))?;
+
+        if let Some(name) = &self.color {
+            if color::parse(name).is_none() {
+                return Err(format!("Unknown color `{}` for module {}", name, module.name()));
+            }
+        }
+
+        // The label can use the module's placeholders, and the format can also use the label and value
+        let placeholders: Vec<&str> = module.placeholders().iter().map(|(name, _)| *name).collect();
+        let line_placeholders: Vec<&str> = ["label", "value"].into_iter().chain(placeholders.iter().copied()).collect();
+        for (option, template, allowed) in [("label", &self.label, &placeholders), ("format", &self.format, &line_placeholders)] {
+            let template = match template {
+                Some(template) => Template::parse(template).map_err(|e| format!("Invalid {} for module {}: {}", option, module.name(), e))?,
+                None => continue
+            };
+            let unknown = template.placeholders().find(|name| !allowed.contains(name)).map(str::to_string);
+            if let Some(unknown) = unknown {
+                return Err(format!(
+                    "Unknown placeholder `{{{}}}` in the {} of module {}, expected one of: {}",
+                    unknown, option, module.name(),
+                    allowed.iter().map(|name| format!("{{{}}}", name)).collect::<Vec<_>>().join(", ")
+                ));
+            }
+        }
+
+        Ok(module)
+    }
+}
+
+impl Serialize for ModuleOptions {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ModuleOptions::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ModuleOptions {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<ModuleOptions, D::Error> {
+        struct EntryVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for EntryVisitor {
+            type Value = ModuleOptions;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a module name or a table of module options")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<ModuleOptions, E> {
+                Ok(ModuleOptions::new(name))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, map: A) -> Result<ModuleOptions, A::Error> {
+                ModuleOptions::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+            }
+        }
+
+        deserializer.deserialize_any(EntryVisitor)
+    }
+}
+
+impl Config {
+    /// Load the config from a file, along with the problems found in it
+    ///
+    /// The files it includes are loaded first, and then the given profile, or else the first
+    /// profile whose hosts match the hostname, is applied over the file's settings. Unknown keys,
+    /// values of the wrong type, unknown profiles and files that cannot be included are ignored,
+    /// and invalid `modules` entries are skipped when the config is used, but all of them are
+    /// returned as problems.
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<(Config, Vec<ConfigError>), ConfigError> {
+        let mut problems = Vec::new();
+        let mut settings = read_settings(path, &mut Vec::new(), &mut problems)?;
+
+        // Apply the selected profile
+        let profiles = match settings.remove("profiles") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            _ => toml::value::Table::new()
+        };
+        let selected = match profile {
+            Some(name) => {
+                if !profiles.contains_key(name) {
+                    let names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                    problems.push(ConfigError::Invalid {
+                        path: path.to_path_buf(),
+                        position: None,
+                        message: match names.as_slice() {
+                            [] => format!("Unknown profile `{}`, no profiles are defined", name),
+                            names => format!("Unknown profile `{}`, expected one of: {}", name, names.join(", "))
+                        }
+                    });
+                }
+                profiles.get(name)
+            },
+            None => {
+                let hostname = whoami::hostname();
+                profiles.values().find(|profile| match profile.get("hosts") {
+                    Some(toml::Value::Array(hosts)) => hosts.iter().any(|host| host.as_str().is_some_and(|host| host_matches(host, &hostname))),
+                    _ => false
+                })
+            }
+        };
+        if let Some(toml::Value::Table(profile)) = selected {
+            let mut profile = profile.clone();
+            profile.remove("hosts");
+            merge(&mut settings, profile);
+        }
+
+        let config = toml::Value::Table(settings).try_into().map_err(|e| ConfigError::Parse(path.to_path_buf(), e))?;
+        Ok((config, problems))
+    }
+
+    /// Override settings with the `OXIFETCH_*` environment variables named after them, such as
+    /// `OXIFETCH_IMAGE_NAME` for `image_name`
+    ///
+    /// Booleans are `true` or `false`, and lists such as `OXIFETCH_MODULES` are separated by commas. Variables with invalid values are returned as problems and leave their setting unchanged.
+    pub fn apply_env(&mut self) -> Vec<ConfigError> {
+        let mut problems = Vec::new();
+
+        // Settings are changed through their TOML representation, so the value of each variable is
+        // interpreted like the value it replaces
+        let mut table = match toml::Value::try_from(&*self) {
+            Ok(toml::Value::Table(table)) => table,
+            _ => return problems
+        };
+        let keys: Vec<String> = table.keys().cloned().chain(std::iter::once("modules".to_string())).collect();
+
+        let mut changed = false;
+        for key in keys {
+            let variable = format!("{}{}", ENV_PREFIX, key.to_uppercase());
+            let value = match std::env::var(&variable) {
+                Ok(value) => value,
+                Err(_) => continue
+            };
+
+            let setting = match table.get(&key) {
+                Some(toml::Value::Boolean(_)) => parse_bool(&value).map(toml::Value::Boolean).ok_or("expected true or false"),
+                Some(toml::Value::Integer(_)) => value.trim().parse().map(toml::Value::Integer).map_err(|_| "expected a whole number"),
+                // Lists, including `modules` when it is not set
+                Some(toml::Value::Array(_)) | None => Ok(toml::Value::Array(value.split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| toml::Value::String(name.to_string()))
+                    .collect())),
+                _ => Ok(toml::Value::String(value))
+            };
+
+            // Check the value against the setting's type before keeping it
+            let mut updated = table.clone();
+            match setting {
+                Ok(setting) => updated.insert(key, setting),
+                Err(e) => {
+                    problems.push(ConfigError::Env(variable, e.to_string()));
+                    continue;
+                }
+            };
+            match toml::Value::Table(updated.clone()).try_into::<Config>() {
+                Ok(_) => {
+                    table = updated;
+                    changed = true;
+                },
+                Err(e) => problems.push(ConfigError::Env(variable, e.to_string()))
+            }
+        }
+
+        if changed {
+            if let Ok(config) = toml::Value::Table(table).try_into::<Config>() {
+                *self = config;
+            }
+        }
+
+        // Check the modules and colors given in the environment
+        let variable = format!("{}MODULES", ENV_PREFIX);
+        if std::env::var_os(&variable).is_some() {
+            for options in self.modules.iter().flatten() {
+                if let Err(e) = options.check() {
+                    problems.push(ConfigError::Env(variable.clone(), e));
+                }
+            }
+        }
+        let variable = format!("{}LOGO_COLORS", ENV_PREFIX);
+        if std::env::var_os(&variable).is_some() {
+            for name in self.logo_colors.iter().filter(|name| logo::palette_color(name).is_none()) {
+                problems.push(ConfigError::Env(variable.clone(), format!("Unknown color `{}`", name)));
+            }
+        }
+
+        problems
+    }
+
+    /// Whether the module with the given name is enabled
+    pub fn is_enabled(&self, module: &str) -> bool {
+        match module {
+            "user" => self.user,
+            "partition" => self.partition,
+            "os" => self.os,
+            "computer_name" => self.computer_name,
+            "kernel_version" => self.kernel_version,
+            "uptime" => self.uptime,
+            "resolution" => self.resolution,
+            "packages" => self.packages,
+            "theme" => self.theme,
+            "cpu_name" => self.cpu_name,
+            "gpu_info" => self.gpu_info,
+            "processes" => self.processes,
+            "ram" => self.ram,
+            "swap" => self.swap,
+            "disk_info" => self.disk_info,
+            "battery" => self.battery,
+            "locale" => self.locale,
+            "weather" => self.weather,
+            "colors" => self.colors,
+            _ => false
+        }
+    }
+
+    /// The modules to show in order, from the `modules` list or else the module switches
+    pub fn module_list(&self) -> Vec<ModuleOptions> {
+        let list = match &self.modules {
+            Some(modules) => modules.clone(),
+            None => modules::registry().iter()
+                .filter(|module| self.is_enabled(module.name()))
+                .map(|module| ModuleOptions::new(module.name()))
+                .collect()
+        };
+
+        // The weather can only be fetched with an API key
+        list.into_iter().filter(|options| {
+            !self.weather_api_key.is_empty() || modules::find(&options.name).is_none_or(|module| module.name() != "weather")
+        }).collect()
+    }
+}
+
+// Generated config //
+
+// Settings before the module switches, as a comment and the line that sets the default
+const GENERAL_SETTINGS: &[(&str, &str)] = &[
+    ("Logo printed beside the information, see `oxifetch --list-logos`, \"auto\" for the running\n# system's, or the path of a PNG or JPEG image", "image_name = \"auto\""),
+    ("Number of lines between the top of the image and the first line of information", "info_offset = 0"),
+    ("Where the logo is printed: \"left\", \"right\", \"top\", \"bottom\" or \"none\"", "logo_position = \"left\""),
+    ("Columns of space before every line", "logo_padding = 0"),
+    ("Space between the logo and the information, in columns when beside each other or lines\n# when above each other", "logo_gap = 1"),
+    ("Size of PNG and JPEG images set as image_name, in columns and rows, where 0 keeps the\n# image's aspect ratio", "image_columns = 32\nimage_rows = 0"),
+    ("Colors images are printed with: \"auto\", \"truecolor\", \"256\" or \"16\"", "image_colors = \"auto\""),
+    ("How images are printed: \"auto\", \"kitty\", \"iterm\", \"sixel\" or\n# \"blocks\" for half-block characters", "image_protocol = \"auto\""),
+    ("Extra logo files and directories, see the README for their format", "logo_paths = []"),
+    ("Colors replacing the logo's own, such as [\"blue\", \"#ff8800\"] for its first two colors", "logo_colors = []"),
+    ("Output format: \"text\", \"json\", \"prometheus\", \"toml\", \"yaml\" or \"env\"", "format = \"text\""),
+    ("When colors are printed: \"auto\" when printing to a terminal and NO_COLOR is not set,\n# \"always\" or \"never\"", "color = \"auto\""),
+    ("Refuse to run when this file has problems, instead of skipping the settings that are invalid", "strict = false"),
+    ("OpenWeatherMap API key, which the weather module needs to fetch the weather", "weather_api_key = \"\""),
+    ("Number of colors the colors module shows: 8, 16 or 256", "palette_colors = 16")
+];
+
+// Example of the modules list, commented out in the generated config
+const MODULES_EXAMPLE: &str = r##"
+# The modules to show in order, replacing the switches above when set. Each entry is
+# a module name or alias, or a table of these options:
+#   name           Name or alias of the module, see `oxifetch --list-modules`
+#   label          Replaces the label printed before the module's values, and may
+#                  use the module's placeholders
+#   format         Layout of each line, where {label}, {value}, and the module's
+#                  placeholders are replaced, and {{ and }} are literal braces
+#   color          Color of the label, a name such as "dark_blue" or a hex color
+#                  such as "#ff8800"
+#   hide_if_empty  Skip lines whose information is unavailable instead of printing
+#                  "Unknown"
+#
+# There are no unit settings, instead each module has placeholders for its values in
+# different units, such as {used} in GiB and {used_bytes} in bytes for memory. Numbers
+# take a precision such as {used:.1}.
+#
+# modules = [
+#     { name = "user", color = "cyan" },
+#     "partition",
+#     "os",
+#     { name = "kernel", label = "Linux" },
+#     { name = "cpu", format = "{label}: {model} ({cores}) @ {ghz:.2} GHz" },
+#     { name = "memory", format = "{label}: {used:.1} / {total:.1} GiB ({percent}%)" },
+#     { name = "disk", label = "{mount}", format = "{label}: {used} / {total} GiB [{fs}]" },
+#     { name = "battery", hide_if_empty = true },
+# ]
+
+# Other config files whose settings this file builds on, relative to this file. The
+# settings in this file take precedence over the included ones.
+#
+# include = ["team.toml"]
+
+# Profiles override the settings above when selected with `oxifetch --profile NAME`
+# or OXIFETCH_PROFILE. Otherwise the first profile, by name, with a `hosts` pattern
+# matching the hostname is used, where * matches any characters.
+#
+# [profiles.motd]
+# modules = ["os", "kernel", "uptime"]
+# image_name = "windows_11"
+#
+# [profiles.server]
+# hosts = ["web-*", "db01"]
+# modules = ["user", "partition", "os", "uptime", "memory", "disk"]
+"##;
+
+impl Config {
+    /// A config file documenting every setting, with the modules that work without setup enabled
+    ///
+    /// The names of the available logos are listed with `image_name`.
+    pub fn generate<'a>(logos: impl IntoIterator<Item = &'a str>) -> String {
+        let mut lines = vec![
+            "# OxiFetch config".to_string(),
+            "#".to_string(),
+            "# Check this file for problems with `oxifetch --check-config`. Every setting can also be".to_string(),
+            "# set with an environment variable named after it, such as OXIFETCH_IMAGE_NAME, which".to_string(),
+            "# takes precedence over this file.".to_string()
+        ];
+
+        for (comment, setting) in GENERAL_SETTINGS {
+            lines.push(String::new());
+            lines.push(format!("# {}", comment));
+            lines.push(setting.to_string());
+        }
+
+        // Each module's switch, with its placeholders
+        lines.push(String::new());
+        lines.push("# Modules shown when the modules list below is not set, in this order".to_string());
+        for module in modules::registry() {
+            let mut comment = format!("# {}", module.name());
+            if !module.aliases().is_empty() {
+                comment += &format!(" (also {})", module.aliases().join(", "));
+            }
+            let placeholders: Vec<String> = module.placeholders().iter().map(|(name, _)| format!("{{{}}}", name)).collect();
+            comment += &format!(": {}", placeholders.join(", "));
+
+            lines.push(String::new());
+            lines.push(comment);
+            lines.push(format!("{} = {}", module.name(), enabled_by_default(module.name())));
+        }
+
+        let mut config = lines.join("\n");
+        config.push('\n');
+        config.push_str(MODULES_EXAMPLE);
+
+        // The available logos
+        config.push_str("\n# Logos that can be set as image_name:\n");
+        for name in logos {
+            config.push_str(&format!("#   {}\n", name));
+        }
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_matches_generated() {
+        let generated: Config = toml::from_str(&Config::generate(["linux"])).unwrap();
+        assert_eq!(toml::Value::try_from(Config::default()).unwrap(), toml::Value::try_from(generated).unwrap());
+        assert!(Config::default().os && !Config::default().weather && !Config::default().colors);
+    }
+
+    #[test]
+    fn host_patterns() {
+        // Without a `*`
+        assert!(host_matches("laptop", "laptop"));
+        assert!(host_matches("Laptop", "LAPTOP"));
+        assert!(!host_matches("laptop", "laptop2"));
+        assert!(!host_matches("laptop", "my-laptop"));
+
+        // `*` at the start, middle and end
+        assert!(host_matches("*-laptop", "work-laptop"));
+        assert!(!host_matches("*-laptop", "work-laptop-2"));
+        assert!(host_matches("work-*-pc", "work-42-pc"));
+        assert!(host_matches("work-*-pc", "work--pc"));
+        assert!(!host_matches("work-*-pc", "work-pc"));
+        assert!(host_matches("build*", "build"));
+        assert!(host_matches("build*", "build-07"));
+        assert!(!host_matches("build*", "rebuild"));
+
+        // Several `*`
+        assert!(host_matches("*", ""));
+        assert!(host_matches("a*b*c", "abc"));
+        assert!(host_matches("a*b*c", "axxbyyc"));
+        assert!(!host_matches("a*b*c", "acb"));
+        assert!(!host_matches("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn wrong_type_drops_only_its_key() {
+        let path = std::env::temp_dir().join(format!("oxifetch-wrong-type-{}.toml", std::process::id()));
+        std::fs::write(&path, "os = \"yes\"\ncpu_name = false\n\n[profiles.work]\nswap = 1\nram = false\n").unwrap();
+        let loaded = Config::load(&path, Some("work"));
+        std::fs::remove_file(&path).unwrap();
+
+        let (config, problems) = loaded.unwrap();
+        assert!(!config.cpu_name && !config.ram);
+        assert!(config.os && config.swap);
+        let problems: Vec<String> = problems.iter().map(|problem| problem.to_string()).collect();
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].ends_with(":5:1: Invalid value for `swap` in profile work: invalid type: integer `1`, expected a boolean"));
+        assert!(problems[1].ends_with(":1:1: Invalid value for `os`: invalid type: string \"yes\", expected a boolean"));
+    }
+}
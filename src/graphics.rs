@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+use std::io::{Cursor, IsTerminal};
+#[cfg(unix)]
+use std::io::Write;
+#[cfg(unix)]
+use std::time::{Duration, Instant};
+
+use base64::Engine;
+use image::imageops::FilterType;
+use image::RgbaImage;
+use serde::{Serialize, Deserialize};
+
+// Graphics protocols //
+
+/// Size of the base64 data in each escape sequence of the Kitty graphics protocol
+const KITTY_CHUNK: usize = 4096;
+
+/// Size of a cell in pixels assumed for Sixel images, as the terminal draws them pixel for pixel
+pub const CELL_WIDTH: u32 = 10;
+pub const CELL_HEIGHT: u32 = 20;
+
+/// Most colors of a Sixel image, as many terminals have no more color registers
+const SIXEL_COLORS: usize = 256;
+
+/// How long to wait for the terminal to reply to a query
+#[cfg(unix)]
+const QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Ways of printing image files
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    /// Detected from the terminal's environment variables, or else by asking the terminal whether
+    /// it supports Sixel graphics
+    #[default]
+    Auto,
+    /// The Kitty graphics protocol, supported by Kitty, WezTerm, Ghostty and Konsole
+    Kitty,
+    /// iTerm2's inline images, supported by iTerm2 and WezTerm
+    Iterm,
+    /// Sixel graphics, supported by foot, mlterm, and xterm with `-ti vt340`
+    Sixel,
+    /// Half-block characters, which work in any terminal with colors
+    Blocks
+}
+
+impl Protocol {
+    /// The protocol of the terminal, when it is not configured
+    ///
+    /// Half-blocks are used when the output is not a terminal.
+    pub fn detect(self) -> Protocol {
+        if self != Protocol::Auto {
+            return self;
+        }
+        if !std::io::stdout().is_terminal() {
+            return Protocol::Blocks;
+        }
+
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let term = var("TERM");
+        let program = var("TERM_PROGRAM");
+        if std::env::var_os("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || term == "xterm-ghostty"
+            || program == "ghostty" || program == "WezTerm" || std::env::var_os("KONSOLE_VERSION").is_some() {
+            Protocol::Kitty
+        } else if program == "iTerm.app" || var("LC_TERMINAL") == "iTerm2" {
+            Protocol::Iterm
+        } else if term.starts_with("foot") || term.starts_with("mlterm") || query_sixel() {
+            Protocol::Sixel
+        } else {
+            Protocol::Blocks
+        }
+    }
+}
+
+// Whether the terminal lists Sixel graphics in its primary device attributes, which it is asked
+// for when it is also the input
+#[cfg(unix)]
+fn query_sixel() -> bool {
+    if !std::io::stdin().is_terminal() {
+        return false;
+    }
+    if crossterm::terminal::enable_raw_mode().is_err() {
+        return false;
+    }
+    let reply = query_terminal("\x1b[c", b'c');
+    crossterm::terminal::disable_raw_mode().ok();
+
+    reply.is_some_and(|reply| has_sixel(&reply))
+}
+
+#[cfg(not(unix))]
+fn query_sixel() -> bool {
+    false
+}
+
+// Write a query to the terminal and read its reply up to the final byte, if it replies in time
+#[cfg(unix)]
+fn query_terminal(query: &str, last: u8) -> Option<String> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(query.as_bytes()).ok()?;
+    stdout.flush().ok()?;
+
+    // Read the reply a byte at a time, so that nothing after it is taken from the input
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut reply = Vec::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let mut input = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        // SAFETY: `input` is a single valid pollfd
+        if remaining.is_zero() || unsafe { libc::poll(&mut input, 1, remaining.as_millis() as libc::c_int) } <= 0 {
+            return None;
+        }
+
+        let mut byte = 0u8;
+        // SAFETY: reads at most one byte into `byte`
+        if unsafe { libc::read(libc::STDIN_FILENO, &mut byte as *mut u8 as *mut libc::c_void, 1) } != 1 {
+            return None;
+        }
+        reply.push(byte);
+        if byte == last {
+            return String::from_utf8(reply).ok();
+        }
+    }
+}
+
+// Whether a reply to the primary device attributes query, such as `ESC[?63;1;4c`, has attribute 4
+// for Sixel graphics
+#[cfg_attr(not(unix), allow(dead_code))]
+fn has_sixel(reply: &str) -> bool {
+    match reply.strip_prefix("\x1b[?").and_then(|attributes| attributes.strip_suffix('c')) {
+        Some(attributes) => attributes.split(';').skip(1).any(|attribute| attribute == "4"),
+        None => false
+    }
+}
+
+/// An image printed with a graphics protocol, which takes up a number of columns and rows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graphic {
+    /// The escape sequences that print the image, leaving the cursor where it was
+    pub escape: String,
+    pub columns: u32,
+    pub rows: u32
+}
+
+/// Encode an image as PNG, scaled down so that it is not much larger than the cells it is shown in
+pub fn png(image: &RgbaImage, columns: u32, rows: u32) -> Result<Vec<u8>, String> {
+    // Assume cells of at most 16 by 32 pixels
+    let (max_width, max_height) = (columns * 16, rows * 32);
+    let scaled;
+    let image = if image.width() > max_width || image.height() > max_height {
+        let scale = f64::min(max_width as f64 / image.width() as f64, max_height as f64 / image.height() as f64);
+        let width = ((image.width() as f64 * scale) as u32).max(1);
+        let height = ((image.height() as f64 * scale) as u32).max(1);
+        scaled = image::imageops::resize(image, width, height, FilterType::Triangle);
+        &scaled
+    } else {
+        image
+    };
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png).map_err(|e| e.to_string())?;
+    Ok(png)
+}
+
+/// Print a PNG image with the Kitty graphics protocol, scaled to the given cells
+///
+/// The data is split over several escape sequences, and the terminal is asked not to move the
+/// cursor or reply.
+pub fn kitty(png: &[u8], columns: u32, rows: u32) -> Graphic {
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&str> = data.as_bytes().chunks(KITTY_CHUNK).map(|chunk| std::str::from_utf8(chunk).unwrap_or_default()).collect();
+
+    let mut escape = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = if index + 1 < chunks.len() { 1 } else { 0 };
+        if index == 0 {
+            escape.push_str(&format!("\x1b_Ga=T,f=100,c={},r={},C=1,q=2,m={};{}\x1b\\", columns, rows, more, chunk));
+        } else {
+            escape.push_str(&format!("\x1b_Gq=2,m={};{}\x1b\\", more, chunk));
+        }
+    }
+
+    Graphic { escape, columns, rows }
+}
+
+/// Print a PNG image with iTerm2's inline images, scaled to the given cells
+pub fn iterm(png: &[u8], columns: u32, rows: u32) -> Graphic {
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    let escape = format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+        png.len(), columns, rows, data
+    );
+    Graphic { escape, columns, rows }
+}
+
+/// Print an image with Sixel graphics, scaled to the given cells
+pub fn sixel(image: &RgbaImage, columns: u32, rows: u32) -> Graphic {
+    let image = image::imageops::resize(image, columns * CELL_WIDTH, rows * CELL_HEIGHT, FilterType::Triangle);
+    Graphic { escape: encode_sixel(&image), columns, rows }
+}
+
+/// Encode an image as Sixel graphics at its size in pixels
+///
+/// The colors are reduced to a palette of at most 256, and repeated columns are run-length
+/// encoded. Pixels that are more than half transparent are left unchanged.
+pub fn encode_sixel(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let opaque = |x: u32, y: u32| {
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        if a < 128 { None } else { Some([r, g, b]) }
+    };
+
+    // Count the colors of the opaque pixels
+    let mut counts: HashMap<[u8; 3], usize> = HashMap::new();
+    for (x, y, _) in image.enumerate_pixels() {
+        if let Some(color) = opaque(x, y) {
+            *counts.entry(color).or_default() += 1;
+        }
+    }
+    let mut colors: Vec<([u8; 3], usize)> = counts.into_iter().collect();
+    colors.sort();
+    let palette = median_cut(colors, SIXEL_COLORS);
+
+    // The palette index of every pixel, where transparent pixels have none
+    let mut nearest: HashMap<[u8; 3], usize> = HashMap::new();
+    let indices: Vec<Option<usize>> = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| {
+        opaque(x, y).map(|color| *nearest.entry(color).or_insert_with(|| closest(&palette, color)))
+    }).collect();
+
+    // Start in a mode where pixels that are not drawn stay unchanged, with the size of the image
+    let mut sixel = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+    for (index, [r, g, b]) in palette.iter().enumerate() {
+        let percent = |value: &u8| (*value as u32 * 100 + 127) / 255;
+        sixel.push_str(&format!("#{};2;{};{};{}", index, percent(r), percent(g), percent(b)));
+    }
+
+    // Each band of six rows is drawn once per color in it, returning to its start in between
+    for top in (0..height).step_by(6) {
+        let band = |x: u32, color: usize| (0..6).filter(|row| {
+            top + row < height && indices[((top + row) * width + x) as usize] == Some(color)
+        }).fold(0u8, |bits, row| bits | 1 << row);
+
+        let mut used: Vec<usize> = (top..(top + 6).min(height))
+            .flat_map(|y| indices[(y * width) as usize..((y + 1) * width) as usize].iter().flatten().copied())
+            .collect();
+        used.sort_unstable();
+        used.dedup();
+
+        for (i, &color) in used.iter().enumerate() {
+            if i != 0 {
+                sixel.push('$');
+            }
+            sixel.push_str(&format!("#{}", color));
+            let characters: Vec<char> = (0..width).map(|x| (b'?' + band(x, color)) as char).collect();
+            push_runs(&mut sixel, &characters);
+        }
+        sixel.push('-');
+    }
+
+    sixel.push_str("\x1b\\");
+    sixel
+}
+
+// Append Sixel characters, writing runs of more than three as `!<count><character>`
+fn push_runs(sixel: &mut String, characters: &[char]) {
+    let mut index = 0;
+    while index < characters.len() {
+        let character = characters[index];
+        let run = characters[index..].iter().take_while(|&&c| c == character).count();
+        if run > 3 {
+            sixel.push_str(&format!("!{}{}", run, character));
+        } else {
+            sixel.extend(std::iter::repeat_n(character, run));
+        }
+        index += run;
+    }
+}
+
+// Reduce colors with their number of pixels to a palette, by splitting the box of colors with the
+// widest range at its median until there are enough boxes, and taking the average of each box
+fn median_cut(colors: Vec<([u8; 3], usize)>, size: usize) -> Vec<[u8; 3]> {
+    let range = |colors: &[([u8; 3], usize)], channel: usize| {
+        let values = colors.iter().map(|(color, _)| color[channel]);
+        values.clone().max().unwrap_or(0) - values.min().unwrap_or(0)
+    };
+    let widest = |colors: &[([u8; 3], usize)]| (0..3).max_by_key(|&channel| range(colors, channel)).unwrap_or(0);
+
+    let mut boxes = vec![colors];
+    boxes.retain(|colors| !colors.is_empty());
+    while boxes.len() < size {
+        // The box with the widest range that can be split
+        let split = boxes.iter().enumerate()
+            .filter(|(_, colors)| colors.len() > 1)
+            .max_by_key(|(_, colors)| range(colors, widest(colors)))
+            .map(|(index, _)| index);
+        let mut colors = match split {
+            Some(index) => boxes.swap_remove(index),
+            None => break
+        };
+
+        let channel = widest(&colors);
+        colors.sort_by_key(|(color, _)| color[channel]);
+        let upper = colors.split_off(colors.len() / 2);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    boxes.iter().map(|colors| {
+        let total: usize = colors.iter().map(|(_, count)| count).sum();
+        let average = |channel: usize| (colors.iter().map(|(color, count)| color[channel] as usize * count).sum::<usize>() / total.max(1)) as u8;
+        [average(0), average(1), average(2)]
+    }).collect()
+}
+
+// The index of the palette color closest to a color
+fn closest(palette: &[[u8; 3]], color: [u8; 3]) -> usize {
+    let distance = |other: &[u8; 3]| (0..3).map(|channel| (other[channel] as i32 - color[channel] as i32).pow(2)).sum::<i32>();
+    (0..palette.len()).min_by_key(|&index| distance(&palette[index])).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+    const CLEAR: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+    // An image from rows of pixels
+    fn image(rows: &[&[Rgba<u8>]]) -> RgbaImage {
+        RgbaImage::from_fn(rows[0].len() as u32, rows.len() as u32, |x, y| rows[y as usize][x as usize])
+    }
+
+    // Compare with a file in tests/golden/sixel, which is written instead with OXIFETCH_UPDATE_GOLDEN set
+    fn golden(name: &str, sixel: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/sixel").join(format!("{}.six", name));
+        if std::env::var_os("OXIFETCH_UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, sixel).unwrap();
+        }
+        assert_eq!(sixel, std::fs::read_to_string(&path).unwrap(), "{}", path.display());
+    }
+
+    #[test]
+    fn kitty_single_chunk() {
+        let graphic = kitty(b"abc", 10, 5);
+        assert_eq!(graphic.escape, "\x1b_Ga=T,f=100,c=10,r=5,C=1,q=2,m=0;YWJj\x1b\\");
+        assert_eq!((graphic.columns, graphic.rows), (10, 5));
+    }
+
+    #[test]
+    fn kitty_chunks() {
+        // 6147 bytes are 8196 base64 characters, in chunks of 4096, 4096 and 4
+        let graphic = kitty(&[0; 6147], 32, 16);
+        let chunks: Vec<&str> = graphic.escape.split_terminator("\x1b\\").collect();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].starts_with("\x1b_Ga=T,f=100,c=32,r=16,C=1,q=2,m=1;"));
+        assert!(chunks[1].starts_with("\x1b_Gq=2,m=1;"));
+        assert!(chunks[2].starts_with("\x1b_Gq=2,m=0;"));
+
+        let data: Vec<&str> = chunks.iter().map(|chunk| chunk.split_once(';').unwrap().1).collect();
+        assert_eq!(data.iter().map(|data| data.len()).collect::<Vec<_>>(), vec![4096, 4096, 4]);
+        assert!(graphic.escape.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn iterm_fields() {
+        let graphic = iterm(b"abc", 10, 5);
+        assert_eq!(graphic.escape, "\x1b]1337;File=inline=1;size=3;width=10;height=5;preserveAspectRatio=1:YWJj\x07");
+    }
+
+    #[test]
+    fn sixel_attribute() {
+        assert!(has_sixel("\x1b[?63;1;2;4;6;9;15;22c"));
+        assert!(has_sixel("\x1b[?62;4c"));
+        // The first number is the terminal's class rather than an attribute
+        assert!(!has_sixel("\x1b[?4;1;2c"));
+        assert!(!has_sixel("\x1b[?65;1;9c"));
+        assert!(!has_sixel("\x1b[?1;2c"));
+        assert!(!has_sixel(""));
+    }
+
+    #[test]
+    fn sixel_solid_pixel() {
+        let sixel = encode_sixel(&image(&[&[RED]]));
+        assert_eq!(sixel, "\x1bP0;1;0q\"1;1;1;1#0;2;100;0;0#0@-\x1b\\");
+        golden("solid", &sixel);
+    }
+
+    #[test]
+    fn sixel_transparent_pixel() {
+        let sixel = encode_sixel(&image(&[&[RED, CLEAR]]));
+        assert!(sixel.ends_with("#0@?-\x1b\\"));
+        golden("transparent", &sixel);
+
+        // Without opaque pixels there are no colors or bands
+        assert_eq!(encode_sixel(&image(&[&[CLEAR]])), "\x1bP0;1;0q\"1;1;1;1-\x1b\\");
+    }
+
+    #[test]
+    fn sixel_bands() {
+        // The seventh row starts a second band
+        let sixel = encode_sixel(&image(&[&[RED][..]; 7]));
+        assert!(sixel.ends_with("#0~-#0@-\x1b\\"));
+        golden("bands", &sixel);
+    }
+
+    #[test]
+    fn sixel_colors_in_band() {
+        // Each color of a band is drawn from its start after a `$`
+        let sixel = encode_sixel(&image(&[&[RED, BLUE]]));
+        assert!(sixel.contains("$"));
+        golden("colors", &sixel);
+    }
+
+    #[test]
+    fn sixel_runs() {
+        let sixel = encode_sixel(&image(&[&[RED, RED, RED, RED, RED, BLUE, BLUE, BLUE]]));
+        assert!(sixel.contains("!5@"));
+        assert!(sixel.contains("???"));
+        assert!(sixel.contains("@@@"));
+        golden("runs", &sixel);
+    }
+
+    #[test]
+    fn sixel_palette_reduced() {
+        // 512 colors are reduced to 256
+        let pixels: Vec<Rgba<u8>> = (0..512u32).map(|i| Rgba([(i % 256) as u8, (i / 2) as u8, (i / 256 * 255) as u8, 255])).collect();
+        let sixel = encode_sixel(&image(&[&pixels]));
+        // Color definitions are the only parts with semicolons, such as `#0;2;100;0;0`
+        let definitions = sixel.split('#').filter(|part| part.split(';').count() == 5).count();
+        assert_eq!(definitions, SIXEL_COLORS);
+        golden("palette", &sixel);
+    }
+
+    #[test]
+    fn median_cut_keeps_few_colors() {
+        let colors = vec![([255, 0, 0], 3), ([0, 0, 255], 1)];
+        let mut palette = median_cut(colors, SIXEL_COLORS);
+        palette.sort();
+        assert_eq!(palette, vec![[0, 0, 255], [255, 0, 0]]);
+    }
+}
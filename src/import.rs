@@ -0,0 +1,134 @@
+use crate::logo::Logo;
+
+// Logo import //
+
+/// Formats of logo files from other fetch programs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Source {
+    /// Art with `${c1}` markers, or the neofetch script with the art of every distribution
+    Neofetch,
+    /// Art with `$1` markers, where `$$` is a literal `$`
+    Fastfetch
+}
+
+impl Source {
+    /// Guess the format of a logo file from its contents
+    pub fn detect(text: &str) -> Source {
+        let fastfetch = text.as_bytes().windows(2).any(|pair| pair[0] == b'$' && pair[1].is_ascii_digit());
+        if fastfetch && !text.contains("${c") && !text.contains("ascii_data") {
+            Source::Fastfetch
+        } else {
+            Source::Neofetch
+        }
+    }
+}
+
+/// Convert a logo file into logos, with the name each logo is given in the file if any
+///
+/// A neofetch script holds the logos of many distributions, while other files hold a single logo.
+pub fn convert(text: &str, source: Source) -> Vec<(Option<String>, Logo)> {
+    match source {
+        Source::Neofetch if text.contains("ascii_data") => neofetch_script(text),
+        Source::Neofetch => vec![(None, Logo::from_text(&neofetch_escapes(text.trim_end_matches('\n'))))],
+        Source::Fastfetch => vec![(None, Logo::from_text(&fastfetch_markers(text.trim_end_matches('\n'))))]
+    }
+}
+
+// Read the logos of neofetch's `get_distro_ascii`, where each is a heredoc after a `set_colors`
+// call, under a case pattern with the distribution's name
+fn neofetch_script(text: &str) -> Vec<(Option<String>, Logo)> {
+    let mut logos = Vec::new();
+    let mut name = None;
+    let mut colors = Vec::new();
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.ends_with(')') && trimmed.contains('"') && !trimmed.contains(['(', '=']) {
+            // A case pattern such as `"Debian"*)` or `"Ubuntu"* | "i3buntu"*)`
+            name = trimmed.split('"').nth(1).map(logo_name);
+        } else if let Some(arguments) = trimmed.strip_prefix("set_colors") {
+            colors = arguments.split_whitespace().map(|color| match color {
+                "fg" => "reset".to_string(),
+                color => color.to_string()
+            }).collect();
+        } else if trimmed.contains("ascii_data") && trimmed.contains("<<") {
+            // The heredoc's delimiter, such as `'EOF'`
+            let delimiter = trimmed.rsplit("<<").next().unwrap_or("").trim_start_matches('-').trim().trim_matches(|c| c == '\'' || c == '"');
+            let art: Vec<String> = lines.by_ref().take_while(|line| line.trim() != delimiter).map(neofetch_escapes).collect();
+            logos.push((name.take(), Logo { lines: art, colors: std::mem::take(&mut colors) }));
+        }
+    }
+
+    logos
+}
+
+// Undo the escaping of neofetch art, which is printed with `printf '%b'` so that `\\` is a single
+// backslash
+fn neofetch_escapes(art: &str) -> String {
+    art.replace("\\\\", "\\")
+}
+
+// A logo name from a distribution's name, such as `arch_small` from `Arch_small`
+fn logo_name(name: &str) -> String {
+    name.to_lowercase().chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect()
+}
+
+// Convert fastfetch's `$1` markers to `${c1}` markers
+fn fastfetch_markers(text: &str) -> String {
+    let mut converted = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('$', Some('$')) => {
+                chars.next();
+                converted.push('$');
+            },
+            ('$', Some(digit)) if digit.is_ascii_digit() => {
+                converted.push_str(&format!("${{c{}}}", digit));
+                chars.next();
+            },
+            (c, _) => converted.push(c)
+        }
+    }
+    converted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neofetch_backslashes() {
+        let art = r"${c1}  /\\
+${c1} /  \\";
+        let logos = convert(art, Source::Neofetch);
+        assert_eq!(logos[0].1.lines, vec![r"${c1}  /\", r"${c1} /  \"]);
+    }
+
+    #[test]
+    fn neofetch_script_logos() {
+        let script = r#"
+        "Arch_small")
+            set_colors 6 7 fg
+            read -rd '' ascii_data <<'EOF'
+${c1}      /\\
+${c1}     /  \\
+EOF
+        ;;
+"#;
+        let logos = convert(script, Source::detect(script));
+        assert_eq!(logos.len(), 1);
+        let (name, logo) = &logos[0];
+        assert_eq!(name.as_deref(), Some("arch_small"));
+        assert_eq!(logo.lines, vec![r"${c1}      /\", r"${c1}     /  \"]);
+        assert_eq!(logo.colors, vec!["6", "7", "reset"]);
+    }
+
+    #[test]
+    fn fastfetch_art() {
+        let logos = convert("$1/\\ $$5\n$2\\/", Source::Fastfetch);
+        assert_eq!(logos[0].1.lines, vec!["${c1}/\\ $5", "${c2}\\/"]);
+    }
+}
@@ -0,0 +1,32 @@
+pub mod color;
+pub mod config;
+pub mod graphics;
+pub mod import;
+pub mod logo;
+pub mod modules;
+pub mod output;
+pub mod paths;
+pub mod raster;
+pub mod render;
+pub mod snapshot;
+pub mod template;
+
+pub use modules::{Context, Module};
+pub use snapshot::SystemSnapshot;
+
+/// Collect the information of the given modules into a snapshot
+pub fn collect_modules<'a>(context: &Context, modules: impl IntoIterator<Item = &'a dyn Module>) -> SystemSnapshot {
+    let mut snapshot = SystemSnapshot::default();
+    for module in modules {
+        module.collect(context, &mut snapshot);
+    }
+    snapshot
+}
+
+/// Collect the information of every module into a snapshot
+///
+/// The weather is skipped, as it needs an API key; use [`collect_modules`] with a [`Context`]
+/// holding one to include it.
+pub fn collect() -> SystemSnapshot {
+    collect_modules(&Context::new(String::new(), Default::default()), modules::registry().iter().map(|module| module.as_ref()))
+}
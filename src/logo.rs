@@ -0,0 +1,455 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crossterm::style::Color;
+use serde::{Serialize, Deserialize};
+use unicode_width::UnicodeWidthStr;
+
+use crate::color;
+use crate::config::{self, ConfigError};
+use crate::import;
+
+// Logos //
+
+/// Logos compiled into the binary, so that a bare binary has a logo for common systems, with
+/// their palettes
+const BUILTIN: &[(&str, &[&str], &str)] = &[
+    ("almalinux", &["red", "dark_yellow", "blue", "green", "yellow"], include_str!("../logos/almalinux.txt")),
+    ("alpine", &["blue"], include_str!("../logos/alpine.txt")),
+    ("arch", &["cyan"], include_str!("../logos/arch.txt")),
+    ("centos", &["yellow", "green", "magenta", "blue"], include_str!("../logos/centos.txt")),
+    ("debian", &["red"], include_str!("../logos/debian.txt")),
+    ("fedora", &["blue", "white"], include_str!("../logos/fedora.txt")),
+    ("gentoo", &["magenta"], include_str!("../logos/gentoo.txt")),
+    ("linux", &["white", "yellow"], include_str!("../logos/linux.txt")),
+    ("nixos", &["blue", "cyan"], include_str!("../logos/nixos.txt")),
+    ("opensuse", &["green"], include_str!("../logos/opensuse.txt")),
+    ("rhel", &["red"], include_str!("../logos/rhel.txt")),
+    ("rocky", &["green"], include_str!("../logos/rocky.txt")),
+    ("ubuntu", &["dark_yellow"], include_str!("../logos/ubuntu.txt")),
+    ("void", &["green"], include_str!("../logos/void.txt"))
+];
+
+/// The Windows logos, in the same format as a logo file
+const BUILTIN_TOML: &str = include_str!("../image.toml");
+
+/// Logo name that selects the logo of the running system
+pub const AUTO: &str = "auto";
+
+/// Logo used by `auto` when there is none for the system
+#[cfg(windows)]
+const GENERIC: &str = "windows_10";
+#[cfg(not(windows))]
+const GENERIC: &str = "linux";
+
+/// Systems whose logo is used for a system without one, for the systems that do not list their
+/// whole family in `ID_LIKE`
+const PARENTS: &[(&str, &str)] = &[
+    ("almalinux", "rhel"),
+    ("centos", "rhel"),
+    ("elementary", "ubuntu"),
+    ("endeavouros", "arch"),
+    ("kali", "debian"),
+    ("linuxmint", "ubuntu"),
+    ("manjaro", "arch"),
+    ("neon", "ubuntu"),
+    ("ol", "rhel"),
+    ("opensuse-leap", "opensuse"),
+    ("opensuse-tumbleweed", "opensuse"),
+    ("pop", "ubuntu"),
+    ("raspbian", "debian"),
+    ("rhel", "fedora"),
+    ("rocky", "rhel"),
+    ("sles", "opensuse"),
+    ("suse", "opensuse"),
+    ("ubuntu", "debian"),
+    ("zorin", "ubuntu")
+];
+
+/// Color of logos without a palette
+const DEFAULT_COLOR: Color = Color::Cyan;
+
+/// Art printed beside the information
+///
+/// Lines can contain color markers: `${c1}` to `${c6}` for the colors of the palette, a color
+/// name such as `${red}` or a hex color such as `${#ff8800}`, and `${reset}` for the terminal's
+/// color. A color lasts until the next marker, including across lines.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "LogoEntry")]
+pub struct Logo {
+    pub lines: Vec<String>,
+    /// Colors of the `${cN}` markers, where the first is also the color the logo starts with, and
+    /// `reset` is the terminal's color
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub colors: Vec<String>
+}
+
+/// A logo in a logo file, either its lines or a table with its palette
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LogoEntry {
+    Lines(Vec<String>),
+    Table {
+        lines: Vec<String>,
+        #[serde(default)]
+        colors: Vec<String>
+    }
+}
+
+impl From<LogoEntry> for Logo {
+    fn from(entry: LogoEntry) -> Logo {
+        match entry {
+            LogoEntry::Lines(lines) => Logo { lines, colors: Vec::new() },
+            LogoEntry::Table { lines, colors } => Logo { lines, colors }
+        }
+    }
+}
+
+/// Part of a logo line printed in one color, where no color is the terminal's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub text: String
+}
+
+// Parse a palette entry, where `reset` is the terminal's color
+pub(crate) fn palette_color(name: &str) -> Option<Option<Color>> {
+    match name {
+        "reset" => Some(None),
+        name => color::parse(name).map(Some)
+    }
+}
+
+/// Display width of a line of spans, where wide characters such as CJK take two columns
+pub fn line_width(line: &[Span]) -> usize {
+    line.iter().map(|span| span.text.width()).sum()
+}
+
+/// Display width of the widest line of spans
+pub fn width(lines: &[Vec<Span>]) -> usize {
+    lines.iter().map(|line| line_width(line)).max().unwrap_or(0)
+}
+
+impl Logo {
+    /// A logo from its art, with one line per line of text
+    pub fn from_text(art: &str) -> Logo {
+        Logo { lines: art.lines().map(str::to_string).collect(), colors: Vec::new() }
+    }
+
+    /// Colors of the palette that are not valid
+    pub fn invalid_colors(&self) -> impl Iterator<Item = &str> {
+        self.colors.iter().map(String::as_str).filter(|name| palette_color(name).is_none())
+    }
+
+    /// Split the lines at their color markers, where the palette entries in `overrides` replace
+    /// the logo's own
+    ///
+    /// Markers that are not colors are printed as they are.
+    pub fn spans(&self, overrides: &[String]) -> Vec<Vec<Span>> {
+        let palette: Vec<Option<Option<Color>>> = (0..self.colors.len().max(overrides.len())).map(|index| {
+            overrides.get(index).and_then(|name| palette_color(name)).or_else(|| self.colors.get(index).and_then(|name| palette_color(name)))
+        }).collect();
+        // Palette entries that are missing or invalid use the first color
+        let first = palette.first().copied().flatten().unwrap_or(Some(DEFAULT_COLOR));
+        let palette_entry = |index: usize| palette.get(index).copied().flatten().unwrap_or(first);
+
+        let mut color = first;
+        self.lines.iter().map(|line| {
+            let mut spans = Vec::new();
+            let mut text = String::new();
+            let mut rest = line.as_str();
+
+            while let Some(start) = rest.find("${") {
+                let marker = rest[start + 2..].find('}').map(|end| &rest[start + 2..start + 2 + end]);
+                let new_color = match marker {
+                    Some("reset") => Some(None),
+                    Some(name) => match name.strip_prefix('c').and_then(|index| index.parse::<usize>().ok()) {
+                        Some(index) if index >= 1 => Some(palette_entry(index - 1)),
+                        _ => color::parse(name).map(Some)
+                    },
+                    None => None
+                };
+
+                match (new_color, marker) {
+                    (Some(new_color), Some(marker)) => {
+                        text.push_str(&rest[..start]);
+                        if !text.is_empty() {
+                            spans.push(Span { color, background: None, text: std::mem::take(&mut text) });
+                        }
+                        color = new_color;
+                        rest = &rest[start + 3 + marker.len()..];
+                    },
+                    // Not a marker, so keep the text as it is
+                    _ => {
+                        text.push_str(&rest[..start + 2]);
+                        rest = &rest[start + 2..];
+                    }
+                }
+            }
+
+            text.push_str(rest);
+            if !text.is_empty() {
+                spans.push(Span { color, background: None, text });
+            }
+            spans
+        }).collect()
+    }
+}
+
+/// Logos by name, loaded from any number of files and directories
+#[derive(Default, Debug)]
+pub struct Logos {
+    logos: BTreeMap<String, Logo>
+}
+
+impl Logos {
+    /// The logos compiled into the binary
+    pub fn builtin() -> Logos {
+        let mut logos = Logos::default();
+        if let Ok(windows) = toml::from_str::<BTreeMap<String, Logo>>(BUILTIN_TOML) {
+            logos.logos.extend(windows);
+        }
+        for (name, colors, art) in BUILTIN {
+            let mut logo = Logo::from_text(art);
+            logo.colors = colors.iter().map(|color| color.to_string()).collect();
+            logos.insert(*name, logo);
+        }
+        logos
+    }
+
+    /// Load the built-in logos and then the given files and directories, where later paths take
+    /// precedence
+    ///
+    /// Files that cannot be loaded are skipped and returned as problems.
+    pub fn load(paths: &[PathBuf]) -> (Logos, Vec<ConfigError>) {
+        let mut logos = Logos::builtin();
+        let mut problems = Vec::new();
+        for path in paths {
+            if let Err(e) = logos.add_path(path) {
+                problems.extend(e);
+            }
+        }
+        (logos, problems)
+    }
+
+    /// Add the logos of a TOML file mapping names to lines, or of a directory of such files and
+    /// text files named after their logo
+    pub fn add_path(&mut self, path: &Path) -> Result<(), Vec<ConfigError>> {
+        if !path.is_dir() {
+            return self.add_file(path).map_err(|e| vec![e]);
+        }
+
+        // Read the directory in order, so that the logo used for a name is predictable
+        let mut files: Vec<PathBuf> = match std::fs::read_dir(path) {
+            Ok(entries) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_file()).collect(),
+            Err(e) => return Err(vec![ConfigError::Io(path.to_path_buf(), e)])
+        };
+        files.sort();
+
+        let problems: Vec<ConfigError> = files.iter().filter_map(|file| self.add_file(file).err()).collect();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    // Add the logos of a TOML file, or the logo of a text file
+    fn add_file(&mut self, path: &Path) -> Result<(), ConfigError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => {
+                let logos: BTreeMap<String, Logo> = config::load_toml(path)?;
+
+                // Skip logos with invalid palettes, reporting the first one
+                let mut invalid = None;
+                for (name, logo) in logos {
+                    let color = logo.invalid_colors().next().map(str::to_string);
+                    match color {
+                        Some(color) => {
+                            invalid.get_or_insert_with(|| format!("Unknown color `{}` in the palette of logo {}", color, name));
+                        },
+                        None => self.insert(name, logo)
+                    }
+                }
+                if let Some(message) = invalid {
+                    return Err(ConfigError::Invalid { path: path.to_path_buf(), position: None, message });
+                }
+            },
+            Some("txt") => {
+                let art = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+                if let Some(name) = path.file_stem().and_then(|name| name.to_str()) {
+                    self.insert(name, Logo::from_text(&art));
+                }
+            },
+            // neofetch and fastfetch art, converted like `oxifetch logo import` does
+            Some(extension @ ("neofetch" | "fastfetch")) => {
+                let art = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+                let source = if extension == "neofetch" { import::Source::Neofetch } else { import::Source::Fastfetch };
+                let stem = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
+                for (name, logo) in import::convert(&art, source) {
+                    self.insert(name.unwrap_or_else(|| stem.to_string()), logo);
+                }
+            },
+            // Other files in a logo directory, such as a README, are not logos
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Add a logo, replacing any logo with the same name
+    pub fn insert(&mut self, name: impl Into<String>, logo: Logo) {
+        self.logos.insert(name.into(), logo);
+    }
+
+    /// The logo with the given name
+    pub fn get(&self, name: &str) -> Option<&Logo> {
+        self.logos.get(name)
+    }
+
+    /// The logo with the given name, or an error listing the available logos
+    pub fn find(&self, name: &str) -> Result<&Logo, String> {
+        match self.get(name) {
+            Some(logo) => Ok(logo),
+            None if self.logos.is_empty() => Err(format!("Unknown logo `{}`, no logos were found", name)),
+            None => Err(format!("Unknown logo `{}`, expected one of: {}", name, self.names().collect::<Vec<_>>().join(", ")))
+        }
+    }
+
+    /// The logo with the given name, or the logo of the running system for `auto` or no name
+    pub fn select(&self, name: &str) -> Result<&Logo, String> {
+        if name.is_empty() || name == AUTO {
+            Ok(self.auto())
+        } else {
+            self.find(name)
+        }
+    }
+
+    /// The logo of the running system
+    ///
+    /// The system's `ID` is tried first, then each system in `ID_LIKE`, then the systems those are
+    /// based on, and finally a generic logo.
+    pub fn auto(&self) -> &Logo {
+        self.auto_for(system_ids())
+    }
+
+    /// The logo of the system with the given IDs, tried in order before the systems they are
+    /// based on and the generic logo
+    pub fn auto_for(&self, ids: Vec<String>) -> &Logo {
+        let mut candidates = ids;
+        let mut index = 0;
+        while index < candidates.len() {
+            if let Some(logo) = self.get(&candidates[index]) {
+                return logo;
+            }
+
+            // Fall back to the system this one is based on
+            if let Some((_, parent)) = PARENTS.iter().find(|(id, _)| *id == candidates[index]) {
+                if !candidates.iter().any(|candidate| candidate == parent) {
+                    candidates.push(parent.to_string());
+                }
+            }
+            index += 1;
+        }
+
+        static EMPTY: Logo = Logo { lines: Vec::new(), colors: Vec::new() };
+        self.get(GENERIC).unwrap_or(&EMPTY)
+    }
+
+    /// Names of the available logos, in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.logos.keys().map(String::as_str)
+    }
+}
+
+// Automatic selection //
+
+// The IDs of the running system, from the first os-release or lsb-release file found
+fn system_ids() -> Vec<String> {
+    for path in ["/etc/os-release", "/usr/lib/os-release"] {
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            return release_ids(&contents);
+        }
+    }
+
+    // Older systems only have lsb-release
+    match std::fs::read_to_string("/etc/lsb-release") {
+        Ok(contents) => release_ids(&contents),
+        Err(_) => Vec::new()
+    }
+}
+
+// The IDs in an os-release file, its `ID` followed by its `ID_LIKE`, or the `DISTRIB_ID` of an
+// lsb-release file
+fn release_ids(contents: &str) -> Vec<String> {
+    let mut ids: Vec<String> = release_value(contents, "ID").or_else(|| release_value(contents, "DISTRIB_ID")).into_iter().collect();
+    if let Some(like) = release_value(contents, "ID_LIKE") {
+        ids.extend(like.split_whitespace().map(str::to_string));
+    }
+    ids
+}
+
+// The lowercase value of a key in an os-release style file, without its quotes
+fn release_value(contents: &str, key: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let (name, value) = line.trim().split_once('=')?;
+        if name.trim() != key {
+            return None;
+        }
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'').to_lowercase();
+        if value.is_empty() { None } else { Some(value) }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_files_like_builtin() {
+        let directory = std::env::temp_dir().join(format!("oxifetch-logos-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("money.txt"), "price $5 only\n").unwrap();
+        std::fs::write(directory.join("nix2.txt"), include_str!("../logos/nixos.txt")).unwrap();
+        std::fs::write(directory.join("tux.fastfetch"), "$1a$$b\n").unwrap();
+
+        let mut logos = Logos::builtin();
+        let added = logos.add_path(&directory);
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert!(added.is_ok());
+
+        assert_eq!(logos.get("money").unwrap().lines, vec!["price $5 only"]);
+        assert_eq!(logos.get("nix2").unwrap().lines, logos.get("nixos").unwrap().lines);
+        assert_eq!(logos.get("tux").unwrap().lines, vec!["${c1}a$b"]);
+    }
+
+    #[test]
+    fn release_values() {
+        let contents = "NAME=\"Pop!_OS\"\nID=pop\nID_LIKE=\"ubuntu debian\"\nVERSION_ID='22.04'\nEMPTY=\"\"\n";
+        assert_eq!(release_value(contents, "ID").as_deref(), Some("pop"));
+        assert_eq!(release_value(contents, "NAME").as_deref(), Some("pop!_os"));
+        assert_eq!(release_value(contents, "VERSION_ID").as_deref(), Some("22.04"));
+        assert_eq!(release_value(contents, "EMPTY"), None);
+        assert_eq!(release_value(contents, "VERSION"), None);
+        assert_eq!(release_ids(contents), vec!["pop", "ubuntu", "debian"]);
+
+        let lsb = "DISTRIB_ID=Ubuntu\nDISTRIB_RELEASE=22.04\n";
+        assert_eq!(release_ids(lsb), vec!["ubuntu"]);
+    }
+
+    #[test]
+    fn auto_fallbacks() {
+        let mut logos = Logos::builtin();
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(logos.auto_for(ids(&["fedora"])), logos.get("fedora").unwrap());
+        assert_eq!(logos.auto_for(ids(&["unknown", "arch"])), logos.get("arch").unwrap());
+        assert_eq!(logos.auto_for(ids(&["pop"])), logos.get("ubuntu").unwrap());
+        assert_eq!(logos.auto_for(ids(&["unknown"])), logos.get(GENERIC).unwrap());
+        assert_eq!(logos.auto_for(Vec::new()), logos.get(GENERIC).unwrap());
+
+        // Pop!_OS falls back to Ubuntu and then to Debian
+        logos.logos.remove("ubuntu");
+        assert_eq!(logos.auto_for(ids(&["pop"])), logos.get("debian").unwrap());
+    }
+}
@@ -0,0 +1,73 @@
+use crate::snapshot::{self, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Battery //
+
+pub struct Battery;
+
+impl Module for Battery {
+    fn name(&self) -> &'static str {
+        "battery"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("percent", "Charge percentage"),
+            ("status", "Charging status, such as Charging or Discharging"),
+            ("health", "Health percentage")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        use libmacchina::traits::BatteryReadout;
+
+        // Create Battery trait
+        let battery: libmacchina::BatteryReadout = libmacchina::traits::BatteryReadout::new();
+
+        // Get battery information
+        snapshot.battery = Some(snapshot::Battery {
+            percent: battery.percentage().ok(),
+            status: battery.status().ok().map(|ac_state| ac_state.to_string()),
+            health_percent: battery.health().ok()
+        });
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let battery = match &snapshot.battery {
+            Some(battery) => battery,
+            None => return vec![Line::new("Battery", None)]
+        };
+
+        let line = Line::new("Battery", match (battery.percent, &battery.status, battery.health_percent) {
+            // If all battery information is available
+            (Some(percentage), Some(ac_state), Some(health)) => {
+                Some(format!("{}% ({}) ({}% Health)", percentage, ac_state, health))
+            }
+            // If battery health is not available
+            (Some(percentage), Some(ac_state), None) => {
+                Some(format!("{}% ({})", percentage, ac_state))
+            }
+            // If charging status is not available
+            (Some(percentage), None, Some(health)) => {
+                Some(format!("{}% ({}% Health)", percentage, health))
+            }
+            // If battery percentage is not available
+            (None, Some(ac_state), Some(health)) => {
+                Some(format!("Unknown% ({}) ({}% Health)", ac_state, health))
+            }
+            // If only charging status is available
+            (None, Some(ac_state), None) => {
+                Some(format!("Unknown% ({})", ac_state))
+            }
+            // If no battery information is available
+            (_, _, _) => None
+        });
+
+        vec![line
+            .with("percent", battery.percent.map(|percent| Value::Int(percent as u64)))
+            .with("status", battery.status.clone().map(Value::Text))
+            .with("health", battery.health_percent.map(Value::Int))]
+    }
+}
@@ -0,0 +1,59 @@
+use crossterm::style::Color;
+
+use crate::color;
+use crate::snapshot::SystemSnapshot;
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Colors //
+
+/// Number of colors in each line of the palette
+const PER_LINE: u16 = 16;
+
+pub struct Colors;
+
+impl Module for Colors {
+    fn name(&self) -> &'static str {
+        "colors"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["palette"]
+    }
+
+    fn needs_colors(&self) -> bool {
+        true
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("blocks", "A block of each color of the line"),
+            ("first", "Number of the line's first color"),
+            ("last", "Number of the line's last color")
+        ]
+    }
+
+    fn collect(&self, context: &Context, snapshot: &mut SystemSnapshot) {
+        // The palette is the terminal's, so there is only its size to gather
+        snapshot.palette = Some(context.palette);
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let count = match snapshot.palette {
+            Some(palette) => palette.colors(),
+            None => return Vec::new()
+        };
+
+        // The 8 normal colors on one line and the bright ones on the next, or 16 per line
+        let per_line = if count == 16 { 8 } else { count.min(PER_LINE) };
+        (0..count).step_by(per_line as usize).map(|first| {
+            let last = (first + per_line).min(count) - 1;
+            let blocks: String = (first..=last).map(|color| format!("{}   {}", color::sgr(Color::AnsiValue(color as u8), true), color::sgr(Color::Reset, true))).collect();
+            Line::new("", Some(blocks.clone()))
+                .with("blocks", Value::Text(blocks))
+                .with("first", Value::Int(first as u64))
+                .with("last", Value::Int(last as u64))
+        }).collect()
+    }
+}
@@ -0,0 +1,33 @@
+use libmacchina::traits::GeneralReadout;
+
+use crate::snapshot::SystemSnapshot;
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Computer name //
+
+pub struct ComputerName;
+
+impl Module for ComputerName {
+    fn name(&self) -> &'static str {
+        "computer_name"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["computer"]
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[("name", "Model of the computer")]
+    }
+
+    fn collect(&self, context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the computer name
+        snapshot.computer_name = context.general.machine().ok();
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        vec![Line::new("Computer", snapshot.computer_name.clone()).with("name", snapshot.computer_name.clone().map(Value::Text))]
+    }
+}
@@ -0,0 +1,141 @@
+use crate::snapshot::{Cpu, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// CPU name //
+
+// https://github.com/GuillaumeGomez/sysinfo/blob/master/src/windows/cpu.rs#L388
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn get_vendor_id_and_brand() -> String {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::__cpuid;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::__cpuid;
+
+    unsafe fn add_u32(v: &mut Vec<u8>, i: u32) {
+        let i = &i as *const u32 as *const u8;
+        v.push(*i);
+        v.push(*i.offset(1));
+        v.push(*i.offset(2));
+        v.push(*i.offset(3));
+    }
+
+    unsafe {
+        // Attempt to get the complete name from the CPU registers
+        let res = __cpuid(0x80000000);
+        let n_ex_ids = res.eax;
+        if n_ex_ids >= 0x80000004 {
+            let mut extdata = Vec::with_capacity(5);
+
+            for i in 0x80000000..=n_ex_ids {
+                extdata.push(__cpuid(i));
+            }
+
+            // 4 * u32 * nb_entries
+            let mut out = Vec::with_capacity(4 * std::mem::size_of::<u32>() * 3);
+            // Iterate over extdata and create vector of utf-8 values
+            for data in extdata.iter().take(5).skip(2) {
+                add_u32(&mut out, data.eax);
+                add_u32(&mut out, data.ebx);
+                add_u32(&mut out, data.ecx);
+                add_u32(&mut out, data.edx);
+            }
+
+            let mut pos = 0;
+            for e in out.iter() {
+                // Stop at the first null byte
+                if *e == 0 {
+                    break;
+                }
+                pos += 1;
+            }
+            // Convert vector of utf-8 values to a string and return the full name
+            match std::str::from_utf8(&out[..pos]) {
+                Ok(s) => s.to_owned(),
+                _ => String::new(),
+            }
+        } else {
+            String::new()
+        }
+    }
+}
+
+pub struct CpuName;
+
+impl Module for CpuName {
+    fn name(&self) -> &'static str {
+        "cpu_name"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["cpu"]
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("model", "Name of the CPU"),
+            ("cores", "Thread count on Windows, core count elsewhere"),
+            ("ghz", "Speed in GHz"),
+            ("mhz", "Speed in MHz")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        let (cores, model);
+
+        // Get the CPU name, thread count, and speed
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_os = "windows"))]{
+            cores = sys_info::cpu_num().ok().map(|cores| cores as usize);
+            model = get_vendor_id_and_brand().trim_end().to_string();
+        }
+
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_os = "windows")))]{
+            use libmacchina::traits::GeneralReadout;
+
+            cores = _context.general.cpu_cores().ok();
+            model = get_vendor_id_and_brand().trim_end().to_string();
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]{
+            use libmacchina::traits::GeneralReadout;
+
+            cores = _context.general.cpu_cores().ok();
+            model = _context.general.cpu_model_name().unwrap_or_default();
+        }
+
+        snapshot.cpu = Some(Cpu {
+            model: Some(model).filter(|model| !model.is_empty()),
+            cores,
+            frequency_mhz: sys_info::cpu_speed().ok().filter(|&mhz| mhz > 0)
+        });
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let cpu = match &snapshot.cpu {
+            Some(cpu) => cpu,
+            None => return vec![Line::new("CPU", None)]
+        };
+
+        // Leave out the parts that are unavailable
+        let value = match (&cpu.model, cpu.cores, cpu.frequency_mhz) {
+            (None, None, None) => None,
+            (model, cores, mhz) => {
+                let mut value = model.clone().unwrap_or_else(|| "Unknown".to_string());
+                if let Some(cores) = cores {
+                    value = format!("{} x {}", cores, value);
+                }
+                if let Some(mhz) = mhz {
+                    value = format!("{} @ {:.1}GHz", value, mhz as f64 / 1000.0);
+                }
+                Some(value)
+            }
+        };
+
+        vec![Line::new("CPU", value)
+            .with("model", cpu.model.clone().map(Value::Text))
+            .with("cores", cpu.cores.map(|cores| Value::Int(cores as u64)))
+            .with("ghz", cpu.frequency_mhz.map(|mhz| Value::Float(mhz as f64 / 1000.0, 1)))
+            .with("mhz", cpu.frequency_mhz.map(Value::Int))]
+    }
+}
@@ -0,0 +1,63 @@
+use sysinfo::{DiskExt, SystemExt};
+
+use crate::snapshot::{self, Disk, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Disk information //
+
+pub struct DiskInfo;
+
+impl Module for DiskInfo {
+    fn name(&self) -> &'static str {
+        "disk_info"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["disk"]
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("mount", "Mount point of the disk, with one line per disk"),
+            ("fs", "File system of the disk"),
+            ("used", "Used space in GiB"),
+            ("total", "Total space in GiB"),
+            ("percent", "Percentage used"),
+            ("used_bytes", "Used space in bytes"),
+            ("total_bytes", "Total space in bytes")
+        ]
+    }
+
+    fn collect(&self, context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get all disks
+        snapshot.disks = Some(context.sys.disks().iter().map(|disk| {
+            let used = disk.total_space() - disk.available_space();
+            Disk {
+                mount_point: disk.mount_point().to_string_lossy().replace('\\', ""),
+                file_system: String::from_utf8_lossy(disk.file_system()).into_owned(),
+                used_bytes: used,
+                total_bytes: disk.total_space(),
+                percent: snapshot::percent(used, disk.total_space())
+            }
+        }).collect());
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        snapshot.disks.iter().flatten().map(|disk| {
+            Line::new(format!("Disk ({})", disk.mount_point), Some(format!("{Used} GB / {Total} GB ({Percent}%)",
+                Used = disk.used_bytes / 1073741824,
+                Total = disk.total_bytes / 1073741824,
+                Percent = disk.percent as u64
+            )))
+                .with("mount", Value::Text(disk.mount_point.clone()))
+                .with("fs", Value::Text(disk.file_system.clone()))
+                .with("used", Value::Float(disk.used_bytes as f64 / 1073741824.00, 0))
+                .with("total", Value::Float(disk.total_bytes as f64 / 1073741824.00, 0))
+                .with("percent", Value::Float(disk.percent, 0))
+                .with("used_bytes", Value::Int(disk.used_bytes))
+                .with("total_bytes", Value::Int(disk.total_bytes))
+        }).collect()
+    }
+}
@@ -0,0 +1,181 @@
+use crate::snapshot::{Gpu, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// GPU name //
+
+#[cfg(target_os = "windows")]
+fn get_gpu_names() -> Vec<String> {
+    // Create the output vector
+    let mut output = Vec::new();
+
+    // Welcome to match statement hell
+    unsafe {
+        use windows_sys::Win32::System::Registry::HKEY;
+        let mut hkey = HKEY::default();
+        // Open the location where some DirectX information is stored
+        match windows_sys::Win32::System::Registry::RegOpenKeyW(
+            windows_sys::Win32::System::Registry::HKEY_LOCAL_MACHINE,
+            "SOFTWARE\\Microsoft\\DirectX\\".encode_utf16().chain([0u16]).collect::<Vec<u16>>().as_mut_ptr(),
+            &mut hkey
+        ) {
+            windows_sys::Win32::Foundation::ERROR_SUCCESS => {
+                // Get the parent key's LastSeen value
+                let mut lastseen = [0u8; 15];
+                let mut size = lastseen.len() as u32;
+                match windows_sys::Win32::System::Registry::RegQueryValueExW(
+                    hkey,
+                    "LastSeen".encode_utf16().chain([0u16]).collect::<Vec<u16>>().as_mut_ptr(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    lastseen.as_mut_ptr(),
+                    &mut size,
+                ) {
+                    windows_sys::Win32::Foundation::ERROR_SUCCESS => {
+                        // Get the parent key's subkey count and the maximum length of the subkeys
+                        let mut key_count = 0;
+                        let mut max_key_len = 0;
+                        match windows_sys::Win32::System::Registry::RegQueryInfoKeyW(
+                            hkey,
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            &mut key_count,
+                            &mut max_key_len,
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                        ) {
+                            windows_sys::Win32::Foundation::ERROR_SUCCESS => {
+                                // Iterate over the parent key's subkeys and find the ones with the same LastSeen value
+                                for i in 1..key_count {
+                                    let mut subkey = [0u16; 50];
+                                    let mut size = max_key_len + 1;
+                                    match windows_sys::Win32::System::Registry::RegEnumKeyExW(
+                                        hkey,
+                                        i,
+                                        subkey.as_mut_ptr(),
+                                        &mut size,
+                                        std::ptr::null_mut(),
+                                        std::ptr::null_mut(),
+                                        std::ptr::null_mut(),
+                                        std::ptr::null_mut(),
+                                    ) {
+                                        windows_sys::Win32::Foundation::ERROR_SUCCESS => {
+                                            // Open the subkey
+                                            let mut subkey_hkey = HKEY::default();
+                                            match windows_sys::Win32::System::Registry::RegOpenKeyW(
+                                                hkey,
+                                                subkey.as_mut_ptr(),
+                                                &mut subkey_hkey
+                                            ) {
+                                                windows_sys::Win32::Foundation::ERROR_SUCCESS => {
+                                                    // Get the subkey's LastSeen value
+                                                    let mut subkey_lastseen = [0u8; 15];
+                                                    let mut size = subkey_lastseen.len() as u32;
+                                                    match windows_sys::Win32::System::Registry::RegQueryValueExW(
+                                                        subkey_hkey,
+                                                        "LastSeen".encode_utf16().chain([0u16]).collect::<Vec<u16>>().as_mut_ptr(),
+                                                        std::ptr::null_mut(),
+                                                        std::ptr::null_mut(),
+                                                        subkey_lastseen.as_mut_ptr(),
+                                                        &mut size
+                                                    ) {
+                                                        windows_sys::Win32::Foundation::ERROR_SUCCESS => {
+                                                            // If the subkey's LastSeen value is the same as the parent key's, get the subkey's Description value
+                                                            if subkey_lastseen == lastseen {
+                                                                let mut description = [0u16; 50];
+                                                                let mut size = (description.len() + 100) as u32;
+                                                                match windows_sys::Win32::System::Registry::RegQueryValueExW(
+                                                                    subkey_hkey,
+                                                                    "Description".encode_utf16().chain([0u16]).collect::<Vec<u16>>().as_mut_ptr(),
+                                                                    std::ptr::null_mut(),
+                                                                    std::ptr::null_mut(),
+                                                                    description.as_mut_ptr() as *mut u8,
+                                                                    &mut size
+                                                                ) {
+                                                                    windows_sys::Win32::Foundation::ERROR_SUCCESS => {
+                                                                        let description_string = String::from_utf16_lossy(&description).trim().replace('\0', "");
+                                                                        // Exclude the Microsoft Basic Render Driver
+                                                                        if description_string != "Microsoft Basic Render Driver" {
+                                                                            // Add the GPU name to the output vector
+                                                                            output.push(description_string.to_string());
+                                                                        }
+                                                                    },
+                                                                    e => { eprintln!("Error {}", e); }
+                                                                }
+                                                            }
+                                                        },
+                                                        e => { eprintln!("Error {}", e); }
+                                                    }
+                                                },
+                                                e => { eprintln!("Error {}", e); }
+                                            }
+                                        },
+                                        e => { eprintln!("Error {}", e); }
+                                    }
+                                }
+                            },
+                            e => { eprintln!("Error {}", e); }
+                        }
+                    },
+                    e => { eprintln!("Error {}", e); }
+                }
+            },
+            e => { eprintln!("Error {}", e); } // TODO: Create alternative for systems without the LastSeen key, which is the case for some systems
+        }
+
+        // Close open key
+        windows_sys::Win32::System::Registry::RegCloseKey(hkey);
+    }
+
+    // Return the output vector
+    output
+}
+
+#[cfg(not(target_os = "windows"))]
+fn get_gpu_names() -> Vec<String> {
+    Vec::new() // TODO: Implement
+}
+
+
+pub struct GpuInfo;
+
+impl Module for GpuInfo {
+    fn name(&self) -> &'static str {
+        "gpu_info"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["gpu"]
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("name", "Name of the GPU, with one line per GPU")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        // Leave the GPUs out if none were found
+        let names = get_gpu_names();
+        if !names.is_empty() {
+            snapshot.gpus = Some(names.into_iter().map(|name| Gpu { name }).collect());
+        }
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let gpus = match &snapshot.gpus {
+            Some(gpus) => gpus,
+            None => return vec![Line::new("GPU", None)]
+        };
+
+        gpus.iter().map(|gpu| {
+            Line::new("GPU", Some(gpu.name.clone())).with("name", Value::Text(gpu.name.clone()))
+        }).collect()
+    }
+}
@@ -0,0 +1,31 @@
+use crate::snapshot::SystemSnapshot;
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Kernel version //
+
+pub struct KernelVersion;
+
+impl Module for KernelVersion {
+    fn name(&self) -> &'static str {
+        "kernel_version"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["kernel"]
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[("version", "Version of the kernel")]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the kernel version
+        snapshot.kernel_version = sys_info::os_release().ok();
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        vec![Line::new("Kernel", snapshot.kernel_version.clone()).with("version", snapshot.kernel_version.clone().map(Value::Text))]
+    }
+}
@@ -0,0 +1,27 @@
+use crate::snapshot::SystemSnapshot;
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Locale //
+
+pub struct Locale;
+
+impl Module for Locale {
+    fn name(&self) -> &'static str {
+        "locale"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[("locale", "Locale of the system, such as en-US")]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the system's locale
+        snapshot.locale = sys_locale::get_locale();
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        vec![Line::new("Locale", snapshot.locale.clone()).with("locale", snapshot.locale.clone().map(Value::Text))]
+    }
+}
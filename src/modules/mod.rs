@@ -0,0 +1,136 @@
+mod battery;
+mod colors;
+mod computer_name;
+mod cpu_name;
+mod disk_info;
+mod gpu_info;
+mod kernel_version;
+mod locale;
+mod os;
+mod packages;
+mod processes;
+mod ram;
+mod resolution;
+mod swap;
+mod theme;
+mod uptime;
+mod user;
+mod weather;
+
+use crate::snapshot::{Palette, SystemSnapshot};
+use crate::template::Value;
+
+
+// Context //
+
+// Readouts shared between modules, created once before any module is collected
+pub struct Context {
+    pub general: libmacchina::GeneralReadout,
+    pub sys: sysinfo::System,
+    pub weather_api_key: String,
+    /// Number of colors shown by the colors module
+    pub palette: Palette
+}
+
+impl Context {
+    pub fn new(weather_api_key: String, palette: Palette) -> Context {
+        use sysinfo::SystemExt;
+
+        // Initialize sysinfo
+        let mut sys: sysinfo::System = SystemExt::new_with_specifics(sysinfo::RefreshKind::new().with_disks());
+        sys.refresh_disks_list();
+
+        Context {
+            // Initialize libmacchina
+            general: libmacchina::traits::GeneralReadout::new(),
+            sys,
+            weather_api_key,
+            palette
+        }
+    }
+}
+
+
+// Module trait //
+
+/// A line of a module's output
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    /// Printed before the value, empty for lines without a label
+    pub label: String,
+    /// The value, or `None` if the information is unavailable
+    pub value: Option<String>,
+    /// Values of the module's placeholders
+    pub fields: Vec<(&'static str, Value)>
+}
+
+impl Line {
+    pub fn new(label: impl Into<String>, value: Option<String>) -> Line {
+        Line { label: label.into(), value, fields: Vec::new() }
+    }
+
+    /// Set the value of one of the module's placeholders, if it is available
+    pub fn with(mut self, name: &'static str, value: impl Into<Option<Value>>) -> Line {
+        if let Some(value) = value.into() {
+            self.fields.push((name, value));
+        }
+        self
+    }
+}
+
+pub trait Module {
+    /// The name of the module, which matches its key in the config
+    fn name(&self) -> &'static str;
+
+    /// Shorter names the module can also be selected by
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Whether the module only shows colors, so that it has no lines when colors are not printed
+    fn needs_colors(&self) -> bool {
+        false
+    }
+
+    /// Placeholders the module's lines provide to its format and label, with a description of each
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Gather the module's information into its field of the snapshot
+    fn collect(&self, context: &Context, snapshot: &mut SystemSnapshot);
+
+    /// Format the module's information from the snapshot into the lines printed beside the image
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line>;
+}
+
+
+// Registry //
+
+// Every available module in the default display order
+pub fn registry() -> Vec<Box<dyn Module>> {
+    vec![
+        Box::new(user::User),
+        Box::new(user::Partition),
+        Box::new(os::Os),
+        Box::new(computer_name::ComputerName),
+        Box::new(kernel_version::KernelVersion),
+        Box::new(uptime::Uptime),
+        Box::new(resolution::Resolution),
+        Box::new(packages::Packages),
+        Box::new(theme::Theme),
+        Box::new(cpu_name::CpuName),
+        Box::new(gpu_info::GpuInfo),
+        Box::new(processes::Processes),
+        Box::new(ram::Ram),
+        Box::new(swap::Swap),
+        Box::new(disk_info::DiskInfo),
+        Box::new(battery::Battery),
+        Box::new(locale::Locale),
+        Box::new(weather::Weather),
+        Box::new(colors::Colors)
+    ]
+}
+
+/// Find a module by its name or one of its aliases
+pub fn find(name: &str) -> Option<Box<dyn Module>> {
+    registry().into_iter().find(|module| module.name() == name || module.aliases().contains(&name))
+}
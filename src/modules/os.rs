@@ -0,0 +1,29 @@
+use sysinfo::SystemExt;
+
+use crate::snapshot::SystemSnapshot;
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// OS name //
+
+pub struct Os;
+
+impl Module for Os {
+    fn name(&self) -> &'static str {
+        "os"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[("name", "Name and version of the operating system")]
+    }
+
+    fn collect(&self, context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the OS name
+        snapshot.os = context.sys.long_os_version();
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        vec![Line::new("OS", snapshot.os.clone()).with("name", snapshot.os.clone().map(Value::Text))]
+    }
+}
@@ -0,0 +1,61 @@
+use crate::snapshot::{PackageCount, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Packages //
+
+pub struct Packages;
+
+impl Module for Packages {
+    fn name(&self) -> &'static str {
+        "packages"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("total", "Number of packages installed by every package manager"),
+            ("managers", "Package count of each package manager, such as 635 (Dpkg)")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        #[cfg(target_os = "linux")]{
+            fn count_dpkg() -> usize {
+                use rust_search::SearchBuilder;
+
+                // Set dpkg directory
+                let dpkg_dir = std::path::Path::new("/var/lib/dpkg/info");
+
+                // Sort files and count
+                SearchBuilder::default()
+                    .location(dpkg_dir)
+                    .search_input(".\\.list")
+                    .build()
+                    .count()
+            }
+
+            snapshot.packages = Some(vec![PackageCount { manager: "Dpkg".to_string(), count: count_dpkg() }]);
+        }
+
+        #[cfg(not(target_os = "linux"))] {
+            use libmacchina::traits::PackageReadout;
+
+            // Get all installed packages
+            let packages: libmacchina::PackageReadout = libmacchina::traits::PackageReadout::new();
+            snapshot.packages = Some(packages.count_pkgs().into_iter().map(|(packagemanager, packagecount)| {
+                PackageCount { manager: packagemanager.to_string(), count: packagecount }
+            }).collect());
+        }
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let managers = snapshot.packages.as_ref().map(|packages| {
+            packages.iter().map(|package| format!("{} ({})", package.count, package.manager)).collect::<Vec<_>>().join(", ")
+        });
+
+        vec![Line::new("Packages", managers.clone())
+            .with("total", snapshot.packages.as_ref().map(|packages| Value::Int(packages.iter().map(|package| package.count as u64).sum())))
+            .with("managers", managers.map(Value::Text))]
+    }
+}
@@ -0,0 +1,29 @@
+use crate::snapshot::SystemSnapshot;
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Processes //
+
+pub struct Processes;
+
+impl Module for Processes {
+    fn name(&self) -> &'static str {
+        "processes"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("count", "Number of running processes")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the number of processes
+        snapshot.processes = sys_info::proc_total().ok();
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        vec![Line::new("Processes", snapshot.processes.map(|count| count.to_string())).with("count", snapshot.processes.map(Value::Int))] //TODO: Add CPU usage
+    }
+}
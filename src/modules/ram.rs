@@ -0,0 +1,53 @@
+use crate::snapshot::{self, Memory, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// RAM //
+
+pub struct Ram;
+
+impl Module for Ram {
+    fn name(&self) -> &'static str {
+        "ram"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["memory"]
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("used", "Used memory in GiB"),
+            ("total", "Total memory in GiB"),
+            ("percent", "Percentage used"),
+            ("used_bytes", "Used memory in bytes"),
+            ("total_bytes", "Total memory in bytes")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the system's memory information, which is measured in KiB
+        snapshot.memory = sys_info::mem_info().ok().map(|memory| {
+            let used = (memory.total - memory.free) * 1024;
+            let total = memory.total * 1024;
+            Memory { used_bytes: used, total_bytes: total, percent: snapshot::percent(used, total) }
+        });
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let memory = match &snapshot.memory {
+            Some(memory) => memory,
+            None => return vec![Line::new("Memory", None)]
+        };
+
+        let used = memory.used_bytes as f64 / 1073741824.00;
+        let total = memory.total_bytes as f64 / 1073741824.00;
+        vec![Line::new("Memory", Some(format!("{:.2} GB / {:.2} GB ({}%)", used, total, memory.percent as u64)))
+            .with("used", Value::Float(used, 2))
+            .with("total", Value::Float(total, 2))
+            .with("percent", Value::Float(memory.percent, 0))
+            .with("used_bytes", Value::Int(memory.used_bytes))
+            .with("total_bytes", Value::Int(memory.total_bytes))]
+    }
+}
@@ -0,0 +1,41 @@
+use crate::snapshot::{Display, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Resolution //
+
+pub struct Resolution;
+
+impl Module for Resolution {
+    fn name(&self) -> &'static str {
+        "resolution"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("resolutions", "Resolution of every monitor, such as 1920x1080, 2560x1440"),
+            ("count", "Number of monitors")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get monitor information and store the scaled resolutions
+        snapshot.resolution = display_info::DisplayInfo::all().map(|displays| {
+            displays.iter().map(|display| Display {
+                width: (display.width as f32 * display.scale_factor) as u32,
+                height: (display.height as f32 * display.scale_factor) as u32
+            }).collect()
+        });
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let resolutions = snapshot.resolution.as_ref().map(|displays| {
+            displays.iter().map(|display| format!("{}x{}", display.width, display.height)).collect::<Vec<_>>().join(", ")
+        });
+
+        vec![Line::new("Resolution", resolutions.clone())
+            .with("resolutions", resolutions.map(Value::Text))
+            .with("count", snapshot.resolution.as_ref().map(|displays| Value::Int(displays.len() as u64)))]
+    }
+}
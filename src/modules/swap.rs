@@ -0,0 +1,49 @@
+use crate::snapshot::{self, Memory, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Swap //
+
+pub struct Swap;
+
+impl Module for Swap {
+    fn name(&self) -> &'static str {
+        "swap"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("used", "Used swap in GiB"),
+            ("total", "Total swap in GiB"),
+            ("percent", "Percentage used"),
+            ("used_bytes", "Used swap in bytes"),
+            ("total_bytes", "Total swap in bytes")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the system's memory information, which is measured in KiB
+        snapshot.swap = sys_info::mem_info().ok().map(|swap| {
+            let used = (swap.swap_total - swap.swap_free) * 1024;
+            let total = swap.swap_total * 1024;
+            Memory { used_bytes: used, total_bytes: total, percent: snapshot::percent(used, total) }
+        });
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let swap = match &snapshot.swap {
+            Some(swap) => swap,
+            None => return vec![Line::new("Swap", None)]
+        };
+
+        let used = swap.used_bytes as f64 / 1073741824.00;
+        let total = swap.total_bytes as f64 / 1073741824.00;
+        vec![Line::new("Swap", Some(format!("{:.2} GB / {:.2} GB ({}%)", used, total, swap.percent as u64)))
+            .with("used", Value::Float(used, 2))
+            .with("total", Value::Float(total, 2))
+            .with("percent", Value::Float(swap.percent, 0))
+            .with("used_bytes", Value::Int(swap.used_bytes))
+            .with("total_bytes", Value::Int(swap.total_bytes))]
+    }
+}
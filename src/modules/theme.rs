@@ -0,0 +1,38 @@
+use crate::snapshot::{self, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Theme //
+
+pub struct Theme;
+
+impl Module for Theme {
+    fn name(&self) -> &'static str {
+        "theme"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("theme", "Dark or Light")
+        ]
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the current theme
+        snapshot.theme = match dark_light::detect() {
+            dark_light::Mode::Dark    => Some(snapshot::Theme::Dark),
+            dark_light::Mode::Light   => Some(snapshot::Theme::Light),
+            dark_light::Mode::Default => None
+        };
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let theme = snapshot.theme.map(|theme| match theme {
+            snapshot::Theme::Dark  => "Dark".to_string(),
+            snapshot::Theme::Light => "Light".to_string()
+        });
+
+        vec![Line::new("Theme", theme.clone()).with("theme", theme.map(Value::Text))]
+    }
+}
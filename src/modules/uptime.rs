@@ -0,0 +1,70 @@
+use libmacchina::traits::GeneralReadout;
+
+use crate::snapshot::SystemSnapshot;
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// Uptime //
+
+pub struct Uptime;
+
+impl Module for Uptime {
+    fn name(&self) -> &'static str {
+        "uptime"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("days", "Whole days of uptime"),
+            ("hours", "Hours of uptime past the whole days"),
+            ("minutes", "Minutes of uptime past the whole hours"),
+            ("seconds", "Total uptime in seconds")
+        ]
+    }
+
+    fn collect(&self, context: &Context, snapshot: &mut SystemSnapshot) {
+        // Get the uptime
+        snapshot.uptime_seconds = context.general.uptime().ok().map(|uptime| uptime as u64);
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        let uptime = match snapshot.uptime_seconds {
+            Some(uptime) => uptime,
+            None => return vec![Line::new("Uptime", None)]
+        };
+
+        let formatted = format!("{Days}{Hours}{Minutes}",
+            Days =
+                match uptime / 86400 {
+                    0 => "".to_string(),
+                    1 => "1 day ".to_string(),
+                    n => format!("{} days ", n),
+                },
+            Hours =
+                match (uptime % 86400) / 3600 {
+                    0 => "".to_string(),
+                    1 => "1 hour ".to_string(),
+                    n => format!("{} hours ", n),
+                },
+            Minutes =
+                match (uptime % 3600) / 60 {
+                    0 => "".to_string(),
+                    1 => "1 minute ".to_string(),
+                    n => format!("{} minutes ", n),
+                },
+        );
+
+        // Less than a minute has passed if every part is empty
+        let line = match formatted.trim_end() {
+            "" => Line::new("Uptime", Some("0 minutes".to_string())),
+            formatted => Line::new("Uptime", Some(formatted.to_string()))
+        };
+
+        vec![line
+            .with("days", Value::Int(uptime / 86400))
+            .with("hours", Value::Int((uptime % 86400) / 3600))
+            .with("minutes", Value::Int((uptime % 3600) / 60))
+            .with("seconds", Value::Int(uptime))]
+    }
+}
@@ -0,0 +1,74 @@
+use crate::snapshot::{self, SystemSnapshot};
+use crate::template::Value;
+
+use super::{Context, Line, Module};
+
+// User and Hostname //
+
+fn collect_user(snapshot: &mut SystemSnapshot) {
+    // Get the current user and hostname
+    snapshot.user = Some(snapshot::User {
+        username: whoami::username(),
+        hostname: whoami::hostname()
+    });
+}
+
+const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("username", "Name of the current user"),
+    ("hostname", "Name of the computer on the network")
+];
+
+fn get_user(snapshot: &SystemSnapshot) -> Option<String> {
+    snapshot.user.as_ref().map(|user| user.username.clone() + "@" + user.hostname.as_str())
+}
+
+// Add the user and hostname placeholders to a line
+fn with_user(line: Line, snapshot: &SystemSnapshot) -> Line {
+    line
+        .with("username", snapshot.user.as_ref().map(|user| Value::Text(user.username.clone())))
+        .with("hostname", snapshot.user.as_ref().map(|user| Value::Text(user.hostname.clone())))
+}
+
+pub struct User;
+
+impl Module for User {
+    fn name(&self) -> &'static str {
+        "user"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        PLACEHOLDERS
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        collect_user(snapshot);
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        vec![with_user(Line::new("", get_user(snapshot)), snapshot)]
+    }
+}
+
+
+// Partition //
+
+pub struct Partition;
+
+impl Module for Partition {
+    fn name(&self) -> &'static str {
+        "partition"
+    }
+
+    fn placeholders(&self) -> &'static [(&'static str, &'static str)] {
+        PLACEHOLDERS
+    }
+
+    fn collect(&self, _context: &Context, snapshot: &mut SystemSnapshot) {
+        collect_user(snapshot);
+    }
+
+    fn render(&self, snapshot: &SystemSnapshot) -> Vec<Line> {
+        // Create a partition with the length of the user and hostname
+        vec![with_user(Line::new("", get_user(snapshot).map(|user| "-".repeat(user.len()))), snapshot)]
+    }
+}
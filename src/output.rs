@@ -0,0 +1,221 @@
+use serde::Serialize;
+
+use crate::snapshot::{Disk, SystemSnapshot};
+
+// Machine-readable output //
+
+/// Version of the schema of the documents, raised when fields are renamed, removed or change type
+///
+/// Fields may be added without raising it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The collected information, as printed by the formats other than text
+#[derive(Debug, Clone, Serialize)]
+pub struct Report<'a> {
+    pub schema_version: u32,
+    /// Version of OxiFetch that collected the information
+    pub oxifetch_version: &'static str,
+    /// Names of the collected modules, in the order they are shown
+    pub modules: Vec<&'a str>,
+    /// The information of the collected modules, where the rest is null, as is information that
+    /// was unavailable
+    pub system: &'a SystemSnapshot
+}
+
+impl<'a> Report<'a> {
+    pub fn new(snapshot: &'a SystemSnapshot, modules: impl IntoIterator<Item = &'a str>) -> Report<'a> {
+        let mut names: Vec<&str> = Vec::new();
+        for name in modules {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        Report { schema_version: SCHEMA_VERSION, oxifetch_version: env!("CARGO_PKG_VERSION"), modules: names, system: snapshot }
+    }
+}
+
+/// The report as a JSON document
+pub fn json(report: &Report) -> Result<String, String> {
+    serde_json::to_string_pretty(report).map_err(|e| e.to_string())
+}
+
+/// The report in the Prometheus text format, for node_exporter's textfile collector
+///
+/// Metrics of information that was not collected are left out.
+pub fn prometheus(report: &Report) -> String {
+    let system = report.system;
+    let mut metrics = Metrics::default();
+
+    metrics.gauge("oxifetch_info", "Version of OxiFetch and of its output schema", vec![
+        (vec![("version", report.oxifetch_version.to_string()), ("schema_version", report.schema_version.to_string())], 1.0)
+    ]);
+    if system.os.is_some() || system.kernel_version.is_some() {
+        let labels = vec![
+            ("name", system.os.clone().unwrap_or_default()),
+            ("kernel", system.kernel_version.clone().unwrap_or_default())
+        ];
+        metrics.gauge("oxifetch_os_info", "Name of the operating system and version of the kernel", vec![(labels, 1.0)]);
+    }
+    if let Some(user) = &system.user {
+        let labels = vec![("username", user.username.clone()), ("hostname", user.hostname.clone())];
+        metrics.gauge("oxifetch_user_info", "Name of the current user and of the computer", vec![(labels, 1.0)]);
+    }
+    if let Some(uptime) = system.uptime_seconds {
+        metrics.gauge("oxifetch_uptime_seconds", "Time since the system booted", vec![(vec![], uptime as f64)]);
+    }
+    if let Some(cpu) = &system.cpu {
+        if let Some(model) = &cpu.model {
+            metrics.gauge("oxifetch_cpu_info", "Model of the CPU", vec![(vec![("model", model.clone())], 1.0)]);
+        }
+        if let Some(cores) = cpu.cores {
+            metrics.gauge("oxifetch_cpu_cores", "Number of CPU cores, or threads on Windows", vec![(vec![], cores as f64)]);
+        }
+        if let Some(mhz) = cpu.frequency_mhz {
+            metrics.gauge("oxifetch_cpu_frequency_hertz", "Frequency of the CPU", vec![(vec![], mhz as f64 * 1e6)]);
+        }
+    }
+    if let Some(processes) = system.processes {
+        metrics.gauge("oxifetch_processes", "Number of running processes", vec![(vec![], processes as f64)]);
+    }
+    if let Some(packages) = &system.packages {
+        let samples = packages.iter().map(|package| (vec![("manager", package.manager.clone())], package.count as f64)).collect();
+        metrics.gauge("oxifetch_packages", "Number of installed packages by package manager", samples);
+    }
+    for (name, memory) in [("memory", &system.memory), ("swap", &system.swap)] {
+        if let Some(memory) = memory {
+            metrics.gauge(&format!("oxifetch_{}_used_bytes", name), &format!("Used {}", name), vec![(vec![], memory.used_bytes as f64)]);
+            metrics.gauge(&format!("oxifetch_{}_total_bytes", name), &format!("Total {}", name), vec![(vec![], memory.total_bytes as f64)]);
+        }
+    }
+    if let Some(disks) = &system.disks {
+        let labels = |disk: &Disk| vec![("mount", disk.mount_point.clone()), ("fstype", disk.file_system.clone())];
+        metrics.gauge("oxifetch_disk_used_bytes", "Used space of a mounted disk", disks.iter().map(|disk| (labels(disk), disk.used_bytes as f64)).collect());
+        metrics.gauge("oxifetch_disk_total_bytes", "Size of a mounted disk", disks.iter().map(|disk| (labels(disk), disk.total_bytes as f64)).collect());
+    }
+    if let Some(battery) = &system.battery {
+        if let Some(percent) = battery.percent {
+            metrics.gauge("oxifetch_battery_percent", "Charge of the battery", vec![(vec![], percent as f64)]);
+        }
+        if let Some(health) = battery.health_percent {
+            metrics.gauge("oxifetch_battery_health_percent", "Capacity of the battery compared to when it was new", vec![(vec![], health as f64)]);
+        }
+    }
+
+    metrics.0
+}
+
+// Metrics in the Prometheus text format
+#[derive(Default)]
+struct Metrics(String);
+
+impl Metrics {
+    // Add a gauge with its samples, each with its labels
+    fn gauge(&mut self, name: &str, help: &str, samples: Vec<(Vec<(&str, String)>, f64)>) {
+        if samples.is_empty() {
+            return;
+        }
+
+        self.0.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n", name, help, name));
+        for (labels, value) in samples {
+            let labels: Vec<String> = labels.iter().map(|(label, value)| format!("{}=\"{}\"", label, escape_label(value))).collect();
+            if labels.is_empty() {
+                self.0.push_str(&format!("{} {}\n", name, value));
+            } else {
+                self.0.push_str(&format!("{}{{{}}} {}\n", name, labels.join(","), value));
+            }
+        }
+    }
+}
+
+// Escape a label value, where backslashes, quotes and line breaks need a backslash
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// The report as a TOML document, which leaves out the information that is unavailable
+pub fn toml(report: &Report) -> Result<String, String> {
+    // Converting to a value first puts the tables after the other values, as TOML requires
+    let value = toml::Value::try_from(report).map_err(|e| e.to_string())?;
+    toml::to_string_pretty(&value).map_err(|e| e.to_string())
+}
+
+/// The report as a YAML document
+pub fn yaml(report: &Report) -> Result<String, String> {
+    serde_yaml::to_string(report).map_err(|e| e.to_string())
+}
+
+/// Prefix of the variables printed by the env format, which keeps them apart from the
+/// `OXIFETCH_*` variables that override settings
+pub const ENV_PREFIX: &str = "OXIFETCH_INFO_";
+
+/// The report as shell variable assignments that are safe to `eval`
+///
+/// Each variable is named after the path of a field of the information, such as
+/// `OXIFETCH_INFO_OS` and `OXIFETCH_INFO_MEMORY_USED_BYTES`, where lists have a `_COUNT` variable
+/// and a variable per item such as `OXIFETCH_INFO_DISKS_0_MOUNT_POINT`. `OXIFETCH_INFO_MODULES`
+/// is separated by commas like `OXIFETCH_MODULES`. Unavailable information is left out.
+pub fn env(report: &Report) -> Result<String, String> {
+    let mut lines = vec![
+        assignment("SCHEMA_VERSION", &report.schema_version.to_string()),
+        assignment("VERSION", report.oxifetch_version),
+        assignment("MODULES", &report.modules.join(","))
+    ];
+    let system = serde_json::to_value(report.system).map_err(|e| e.to_string())?;
+    env_lines(&mut lines, "", &system);
+
+    Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+}
+
+// Add the assignments of a value, whose variables start with the prefix
+fn env_lines(lines: &mut Vec<String>, prefix: &str, value: &serde_json::Value) {
+    let name = |key: &str| {
+        let key: String = key.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' }).collect();
+        if prefix.is_empty() { key } else { format!("{}_{}", prefix, key) }
+    };
+
+    match value {
+        serde_json::Value::Null => {},
+        serde_json::Value::Object(fields) => {
+            for (key, value) in fields {
+                env_lines(lines, &name(key), value);
+            }
+        },
+        serde_json::Value::Array(items) => {
+            lines.push(assignment(&name("count"), &items.len().to_string()));
+            for (index, item) in items.iter().enumerate() {
+                env_lines(lines, &name(&index.to_string()), item);
+            }
+        },
+        serde_json::Value::String(text) => lines.push(assignment(prefix, text)),
+        value => lines.push(assignment(prefix, &value.to_string()))
+    }
+}
+
+// A variable assignment in double quotes, where the characters the shell expands are escaped
+fn assignment(name: &str, value: &str) -> String {
+    let mut quoted = String::new();
+    for c in value.chars() {
+        if matches!(c, '\\' | '"' | '$' | '`') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    format!("{}{}=\"{}\"", ENV_PREFIX, name, quoted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_names() {
+        let snapshot = SystemSnapshot { os: Some("Linux $HOME".to_string()), ..Default::default() };
+        let report = Report::new(&snapshot, ["os", "cpu_name", "swap"]);
+        let lines = env(&report).unwrap();
+
+        assert!(lines.contains("OXIFETCH_INFO_OS=\"Linux \\$HOME\"\n"));
+        assert!(lines.contains("OXIFETCH_INFO_MODULES=\"os,cpu_name,swap\"\n"));
+        assert!(lines.lines().all(|line| line.starts_with(ENV_PREFIX)));
+    }
+}
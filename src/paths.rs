@@ -0,0 +1,89 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+// File discovery //
+
+/// Name of the directory OxiFetch's files are stored in
+const APP_DIR: &str = "oxifetch";
+
+/// Environment variable that points to the config file
+pub const CONFIG_ENV: &str = "OXIFETCH_CONFIG";
+
+// Split an XDG search path variable, falling back to its default when it is unset or empty
+#[cfg(unix)]
+fn xdg_dirs(variable: &str, default: &str) -> Vec<PathBuf> {
+    let value = env::var(variable).ok().filter(|value| !value.is_empty()).unwrap_or_else(|| default.to_string());
+    env::split_paths(&value).filter(|path| path.is_absolute()).collect()
+}
+
+// The config path given by `--config` or `$OXIFETCH_CONFIG`
+fn explicit_config(explicit: Option<&Path>) -> Option<PathBuf> {
+    match explicit {
+        Some(path) => Some(path.to_path_buf()),
+        None => env::var_os(CONFIG_ENV).filter(|path| !path.is_empty()).map(PathBuf::from)
+    }
+}
+
+/// Every location the config file is searched for, in order of precedence
+///
+/// An explicit path, from `--config` or `$OXIFETCH_CONFIG`, is the only candidate when given.
+pub fn config_candidates(explicit: Option<&Path>) -> Vec<PathBuf> {
+    if let Some(path) = explicit_config(explicit) {
+        return vec![path];
+    }
+
+    let mut candidates = Vec::new();
+
+    // $XDG_CONFIG_HOME, or the platform's equivalent
+    if let Some(dir) = dirs::config_dir() {
+        candidates.push(dir.join(APP_DIR).join("config.toml"));
+    }
+
+    // $XDG_CONFIG_DIRS
+    #[cfg(unix)]
+    for dir in xdg_dirs("XDG_CONFIG_DIRS", "/etc/xdg") {
+        candidates.push(dir.join(APP_DIR).join("config.toml"));
+    }
+
+    candidates
+}
+
+/// The config file that will be loaded
+///
+/// An explicit path is returned even if it does not exist, so that loading it reports the error.
+pub fn find_config(explicit: Option<&Path>) -> Option<PathBuf> {
+    match explicit_config(explicit) {
+        Some(path) => Some(path),
+        None => config_candidates(None).into_iter().find(|path| path.is_file())
+    }
+}
+
+/// Every directory logos are searched for in, in order of precedence
+pub fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    // $XDG_DATA_HOME, or the platform's equivalent
+    if let Some(dir) = dirs::data_dir() {
+        dirs.push(dir.join(APP_DIR));
+    }
+
+    // $XDG_DATA_DIRS
+    #[cfg(unix)]
+    for dir in xdg_dirs("XDG_DATA_DIRS", "/usr/local/share:/usr/share") {
+        dirs.push(dir.join(APP_DIR));
+    }
+
+    dirs
+}
+
+/// Every logo file and directory that exists, in increasing order of precedence
+///
+/// These are `image.toml` and the `logos` directory in each data directory, followed by the extra
+/// paths given in the config.
+pub fn logo_paths(extra: &[PathBuf]) -> Vec<PathBuf> {
+    data_dirs().into_iter().rev()
+        .flat_map(|dir| [dir.join("image.toml"), dir.join("logos")])
+        .chain(extra.iter().cloned())
+        .filter(|path| path.exists())
+        .collect()
+}
@@ -0,0 +1,140 @@
+use std::path::{Path, PathBuf};
+
+use crossterm::style::Color;
+use image::imageops::FilterType;
+use image::RgbaImage;
+use serde::{Serialize, Deserialize};
+
+use crate::color;
+use crate::logo::Span;
+
+// Raster logos //
+
+/// Width of raster logos in columns when none is configured
+pub const DEFAULT_COLUMNS: u32 = 32;
+
+/// Colors that raster logos are printed with
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorDepth {
+    /// Detected from `$COLORTERM` and `$TERM`
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    /// 24-bit colors
+    #[serde(rename = "truecolor")]
+    Truecolor,
+    /// The 256 color palette
+    #[serde(rename = "256")]
+    Ansi256,
+    /// The 16 standard ANSI colors
+    #[serde(rename = "16")]
+    Ansi16
+}
+
+impl ColorDepth {
+    /// The color depth of the terminal, when it is not configured
+    pub fn detect(self) -> ColorDepth {
+        if self != ColorDepth::Auto {
+            return self;
+        }
+
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" || std::env::var_os("WT_SESSION").is_some() {
+            ColorDepth::Truecolor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    // The closest color that can be printed
+    fn color(self, (r, g, b): (u8, u8, u8)) -> Color {
+        match self {
+            ColorDepth::Auto | ColorDepth::Truecolor => Color::Rgb { r, g, b },
+            ColorDepth::Ansi256 => Color::AnsiValue(color::to_ansi256((r, g, b))),
+            ColorDepth::Ansi16 => color::ansi16(color::to_ansi16((r, g, b)))
+        }
+    }
+}
+
+/// Whether a logo name is the path of an image file rather than the name of a logo
+pub fn is_image(name: &str) -> bool {
+    let extension = Path::new(name).extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase);
+    matches!(extension.as_deref(), Some("png" | "jpg" | "jpeg"))
+}
+
+/// The path of an image, where `~/` is the home directory
+pub fn image_path(name: &str) -> PathBuf {
+    match (name.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(name)
+    }
+}
+
+/// Open an image file
+pub fn open(path: &Path) -> Result<RgbaImage, String> {
+    let image = image::open(path).map_err(|e| format!("{}: {}", path.display(), e))?.to_rgba8();
+    if image.width() == 0 || image.height() == 0 {
+        return Err(format!("{}: the image is empty", path.display()));
+    }
+    Ok(image)
+}
+
+/// The number of columns and rows an image takes up, fitting the given ones
+///
+/// A size of 0 is worked out from the other one and the image's aspect ratio, where a character
+/// is about twice as high as it is wide.
+pub fn cell_size(image: &RgbaImage, columns: u32, rows: u32) -> (u32, u32) {
+    let (width, height) = (image.width().max(1) as f64, image.height().max(1) as f64);
+    match (columns, rows) {
+        (0, 0) => cell_size(image, DEFAULT_COLUMNS, 0),
+        (0, rows) => (((rows as f64 * 2.0 * width) / height).round().max(1.0) as u32, rows),
+        (columns, 0) => (columns, ((columns as f64 * height) / (width * 2.0)).round().max(1.0) as u32),
+        size => size
+    }
+}
+
+/// Load an image, scaled to fit the given number of columns and rows for [`half_blocks`]
+pub fn load(path: &Path, columns: u32, rows: u32) -> Result<RgbaImage, String> {
+    let image = open(path)?;
+    let (columns, rows) = cell_size(&image, columns, rows);
+
+    // Each row of characters shows two rows of pixels
+    Ok(image::imageops::resize(&image, columns, rows * 2, FilterType::Triangle))
+}
+
+/// Render an image with half-block characters, where each character shows two pixels above each
+/// other
+///
+/// Transparent pixels are left empty, so the terminal's background shows through.
+pub fn half_blocks(image: &RgbaImage, depth: ColorDepth) -> Vec<Vec<Span>> {
+    let depth = depth.detect();
+    let pixel = |x: u32, y: u32| {
+        if y >= image.height() {
+            return None;
+        }
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        if a < 128 { None } else { Some(depth.color((r, g, b))) }
+    };
+
+    (0..image.height()).step_by(2).map(|y| {
+        let mut spans: Vec<Span> = Vec::new();
+        for x in 0..image.width() {
+            let (text, color, background) = match (pixel(x, y), pixel(x, y + 1)) {
+                (Some(top), Some(bottom)) => ("▀", Some(top), Some(bottom)),
+                (Some(top), None) => ("▀", Some(top), None),
+                (None, Some(bottom)) => ("▄", Some(bottom), None),
+                (None, None) => (" ", None, None)
+            };
+
+            // Join characters of the same colors
+            match spans.last_mut() {
+                Some(span) if span.color == color && span.background == background => span.text.push_str(text),
+                _ => spans.push(Span { color, background, text: text.to_string() })
+            }
+        }
+        spans
+    }).collect()
+}
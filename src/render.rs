@@ -0,0 +1,307 @@
+use std::io::{stdout, Stdout, Write};
+
+use crossterm::{cursor, queue, style::{self, Color}};
+use serde::{Serialize, Deserialize};
+use unicode_width::UnicodeWidthStr;
+
+use crate::color;
+use crate::config::ModuleOptions;
+use crate::graphics::Graphic;
+use crate::logo::{self, Span};
+use crate::modules::{Line, Module};
+use crate::snapshot::SystemSnapshot;
+use crate::template::{Template, Value};
+
+// Rendering //
+
+/// Render the lines of the given modules from a snapshot, applying their options
+///
+/// Without `colors`, the colors of the options and the escape sequences of the modules are left
+/// out, as are the modules that only show colors.
+pub fn lines(snapshot: &SystemSnapshot, layout: &[(Box<dyn Module>, ModuleOptions)], colors: bool) -> Vec<String> {
+    layout.iter().filter(|(module, _)| colors || !module.needs_colors()).flat_map(|(module, options)| {
+        module.render(snapshot).into_iter().filter_map(move |line| format_line(line, options, colors))
+    }).map(|line| if colors { line } else { strip_escapes(&line) }).collect()
+}
+
+// Apply a module's options to one of its lines
+fn format_line(line: Line, options: &ModuleOptions, colors: bool) -> Option<String> {
+    let mut fields = line.fields;
+
+    // Substitute or hide unavailable information
+    let mut value = match line.value.filter(|value| !value.is_empty()) {
+        Some(value) => value,
+        None if options.hide_if_empty => return None,
+        None => "Unknown".to_string()
+    };
+
+    let mut label = match &options.label {
+        Some(label) => render_template(label, &fields),
+        None => line.label
+    };
+    let format = match &options.format {
+        Some(format) => format.as_str(),
+        None if label.is_empty() => "{value}",
+        None => "{label}: {value}"
+    };
+
+    // Color the label, or the value of lines without one
+    if let Some(color) = options.color.as_deref().and_then(color::parse).filter(|_| colors) {
+        if label.is_empty() {
+            value = color::paint(&value, color);
+        } else {
+            label = color::paint(&label, color);
+        }
+    }
+
+    fields.push(("label", Value::Text(label)));
+    fields.push(("value", Value::Text(value)));
+    Some(render_template(format, &fields))
+}
+
+// Substitute values into a template, which is printed as is if it is invalid
+fn render_template(template: &str, fields: &[(&str, Value)]) -> String {
+    match Template::parse(template) {
+        Ok(parsed) => parsed.render(fields),
+        Err(_) => template.to_string()
+    }
+}
+
+// Function to print line of image, padded to the width of the image
+fn print_image_line(index: usize, image: &[Vec<Span>], width: usize, colors: bool, mut stdout: &Stdout) {
+    let line = image.get(index).map_or(&[][..], |line| line.as_slice());
+    for span in line {
+        if !colors {
+            queue!(stdout, style::Print(&span.text)).map_err(|e| eprintln!("Error: {}", e)).ok();
+            continue;
+        }
+        let mut styled = span.text.clone();
+        if let Some(color) = span.color {
+            styled = format!("{}{}{}", color::sgr(color, false), styled, color::sgr(Color::Reset, false));
+        }
+        if let Some(background) = span.background {
+            styled = format!("{}{}{}", color::sgr(background, true), styled, color::sgr(Color::Reset, true));
+        }
+        let printed = queue!(stdout, style::Print(styled));
+        if let Err(e) = printed {
+            eprintln!("Error: {}", e)
+        }
+    }
+
+    // Pad short and missing lines, so the information starts in the same column on every line
+    match queue!(stdout, style::Print(" ".repeat(width.saturating_sub(logo::line_width(line))))) {
+        Ok(_) => {},
+        Err(e) => { eprintln!("Error: {}", e) }
+    }
+}
+
+/// Where the logo is printed relative to the information
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogoPosition {
+    #[default]
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// The logo is not printed
+    None
+}
+
+/// How the logo and the information are laid out
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub position: LogoPosition,
+    /// Columns of space before every line
+    pub padding: usize,
+    /// Columns between the logo and the information beside it, or lines between them above each
+    /// other
+    pub gap: usize,
+    /// Lines between the top of the logo and the first line of information beside it
+    pub info_offset: usize,
+    /// Whether the logo is printed in color
+    pub colors: bool
+}
+
+impl Placement {
+    // The placement without a logo to print
+    fn without_logo(self) -> Placement {
+        Placement { position: LogoPosition::None, ..self }
+    }
+}
+
+// A line without its escape sequences
+fn strip_escapes(line: &str) -> String {
+    let mut text = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip to the final character of the sequence
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            text.push(c);
+        }
+    }
+    text
+}
+
+// Display width of a line, without its escape sequences
+fn display_width(line: &str) -> usize {
+    UnicodeWidthStr::width(strip_escapes(line).as_str())
+}
+
+// Print lines of information, each after the padding
+fn print_info(lines: &[String], padding: &str, mut stdout: &Stdout) {
+    for line in lines {
+        queue!(stdout, style::Print(format!("{}{}\n", padding, line))).map_err(|e| eprintln!("Error: {}", e)).ok();
+    }
+}
+
+/// Print the lines and the image as placed
+pub fn print(image: &[Vec<Span>], lines: &[String], placement: &Placement) {
+    // Create stdout variable
+    let mut stdout = stdout();
+
+    let placement = if image.is_empty() { placement.without_logo() } else { *placement };
+    let padding = " ".repeat(placement.padding);
+
+    // Display width of the widest line of the image
+    let width = logo::width(image);
+
+    match placement.position {
+        LogoPosition::Left | LogoPosition::Right => {
+            let info_width = lines.iter().map(|line| display_width(line)).max().unwrap_or(0);
+            let rows = image.len().max(placement.info_offset + lines.len());
+
+            for i in 0..rows {
+                let info = i.checked_sub(placement.info_offset).and_then(|i| lines.get(i));
+                queue!(stdout, style::Print(&padding)).map_err(|e| eprintln!("Error: {}", e)).ok();
+
+                if placement.position == LogoPosition::Left {
+                    print_image_line(i, image, width, placement.colors, &stdout);
+                    if let Some(info) = info {
+                        queue!(stdout, style::Print(" ".repeat(placement.gap)), style::Print(info)).map_err(|e| eprintln!("Error: {}", e)).ok();
+                    }
+                } else {
+                    // Pad the information, so the image starts in the same column on every line
+                    let info = info.map_or("", |info| info.as_str());
+                    queue!(stdout, style::Print(info)).map_err(|e| eprintln!("Error: {}", e)).ok();
+                    if i < image.len() {
+                        let space = info_width - display_width(info) + placement.gap;
+                        queue!(stdout, style::Print(" ".repeat(space))).map_err(|e| eprintln!("Error: {}", e)).ok();
+                        print_image_line(i, image, 0, placement.colors, &stdout);
+                    }
+                }
+                queue!(stdout, style::Print("\n")).map_err(|e| eprintln!("Error: {}", e)).ok();
+            }
+        },
+        LogoPosition::Top | LogoPosition::Bottom => {
+            if placement.position == LogoPosition::Bottom {
+                print_info(lines, &padding, &stdout);
+                queue!(stdout, style::Print("\n".repeat(placement.gap))).map_err(|e| eprintln!("Error: {}", e)).ok();
+            }
+            for i in 0..image.len() {
+                queue!(stdout, style::Print(&padding)).map_err(|e| eprintln!("Error: {}", e)).ok();
+                print_image_line(i, image, 0, placement.colors, &stdout);
+                queue!(stdout, style::Print("\n")).map_err(|e| eprintln!("Error: {}", e)).ok();
+            }
+            if placement.position == LogoPosition::Top {
+                queue!(stdout, style::Print("\n".repeat(placement.gap))).map_err(|e| eprintln!("Error: {}", e)).ok();
+                print_info(lines, &padding, &stdout);
+            }
+        },
+        LogoPosition::None => print_info(lines, &padding, &stdout)
+    }
+
+    // Print the output queue
+    stdout.flush().map_err(|e| eprintln!("Error: {}", e)).ok();
+}
+
+// Print an image with a graphics protocol in a column, leaving the cursor in that column on the
+// image's top line
+fn print_graphic_image(graphic: &Graphic, column: usize, mut stdout: &Stdout) {
+    let rows = graphic.rows as usize;
+
+    // Make room for the image first, so that the terminal scrolls before it is printed
+    if rows != 0 {
+        queue!(stdout, style::Print("\n".repeat(rows)), cursor::MoveUp(rows as u16)).map_err(|e| eprintln!("Error: {}", e)).ok();
+    }
+
+    // Print the image without moving the cursor
+    queue!(
+        stdout,
+        cursor::MoveToColumn(column.min(u16::MAX as usize) as u16),
+        cursor::SavePosition,
+        style::Print(&graphic.escape),
+        cursor::RestorePosition
+    ).map_err(|e| eprintln!("Error: {}", e)).ok();
+}
+
+/// Print the lines and an image printed with a graphics protocol as placed
+pub fn print_graphic(graphic: &Graphic, lines: &[String], placement: &Placement) {
+    let mut stdout = stdout();
+    let rows = graphic.rows as usize;
+    let padding = " ".repeat(placement.padding);
+
+    match placement.position {
+        LogoPosition::Left | LogoPosition::Right => {
+            let info_width = lines.iter().map(|line| display_width(line)).max().unwrap_or(0);
+            let (image_column, info_column) = match placement.position {
+                LogoPosition::Left => (placement.padding, placement.padding + graphic.columns as usize + placement.gap),
+                _ => (placement.padding + info_width + placement.gap, placement.padding)
+            };
+            print_graphic_image(graphic, image_column, &stdout);
+
+            // Print the lines in their column beside the image
+            queue!(stdout, style::Print("\n".repeat(placement.info_offset))).map_err(|e| eprintln!("Error: {}", e)).ok();
+            let column = info_column.min(u16::MAX as usize) as u16;
+            for line in lines {
+                queue!(stdout, cursor::MoveToColumn(column), style::Print(format!("{}\n", line))).map_err(|e| eprintln!("Error: {}", e)).ok();
+            }
+
+            // Move below the image
+            let printed = placement.info_offset + lines.len();
+            if printed < rows {
+                queue!(stdout, style::Print("\n".repeat(rows - printed))).map_err(|e| eprintln!("Error: {}", e)).ok();
+            }
+        },
+        LogoPosition::Top | LogoPosition::Bottom => {
+            if placement.position == LogoPosition::Bottom {
+                print_info(lines, &padding, &stdout);
+                queue!(stdout, style::Print("\n".repeat(placement.gap))).map_err(|e| eprintln!("Error: {}", e)).ok();
+            }
+            print_graphic_image(graphic, placement.padding, &stdout);
+            queue!(stdout, style::Print("\n".repeat(rows))).map_err(|e| eprintln!("Error: {}", e)).ok();
+            if placement.position == LogoPosition::Top {
+                queue!(stdout, style::Print("\n".repeat(placement.gap))).map_err(|e| eprintln!("Error: {}", e)).ok();
+                print_info(lines, &padding, &stdout);
+            }
+        },
+        LogoPosition::None => print_info(lines, &padding, &stdout)
+    }
+
+    stdout.flush().map_err(|e| eprintln!("Error: {}", e)).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::Palette;
+
+    #[test]
+    fn colors_module_without_colors() {
+        let snapshot = SystemSnapshot { os: Some("Linux".to_string()), palette: Some(Palette::default()), ..Default::default() };
+        let layout: Vec<(Box<dyn Module>, ModuleOptions)> = ["os", "colors"].iter().map(|name| {
+            (crate::modules::find(name).unwrap(), ModuleOptions { name: name.to_string(), ..Default::default() })
+        }).collect();
+
+        assert_eq!(lines(&snapshot, &layout, false), vec!["OS: Linux"]);
+        assert_eq!(lines(&snapshot, &layout, true).len(), 3);
+    }
+}
@@ -0,0 +1,151 @@
+use serde::{Serialize, Deserialize};
+
+// System snapshot //
+
+/// Typed information gathered by the modules
+///
+/// Each field is filled in by the module of the same name, and is `None` if that module was not
+/// collected or its information was unavailable.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    pub user: Option<User>,
+    pub os: Option<String>,
+    pub computer_name: Option<String>,
+    pub kernel_version: Option<String>,
+    pub uptime_seconds: Option<u64>,
+    pub resolution: Option<Vec<Display>>,
+    pub packages: Option<Vec<PackageCount>>,
+    pub theme: Option<Theme>,
+    pub cpu: Option<Cpu>,
+    pub gpus: Option<Vec<Gpu>>,
+    pub processes: Option<u64>,
+    pub memory: Option<Memory>,
+    pub swap: Option<Memory>,
+    pub disks: Option<Vec<Disk>>,
+    pub battery: Option<Battery>,
+    pub locale: Option<String>,
+    pub weather: Option<Weather>,
+    /// Number of colors of the terminal's palette shown by the colors module
+    pub palette: Option<Palette>
+}
+
+
+// Readouts //
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    pub username: String,
+    pub hostname: String
+}
+
+/// Scaled resolution of a monitor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Display {
+    pub width: u32,
+    pub height: u32
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageCount {
+    pub manager: String,
+    pub count: usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Dark,
+    Light
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cpu {
+    pub model: Option<String>,
+    /// Thread count on Windows, core count elsewhere
+    pub cores: Option<usize>,
+    pub frequency_mhz: Option<u64>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Gpu {
+    pub name: String
+}
+
+/// Usage of RAM or swap
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Memory {
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+    pub percent: f64
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Disk {
+    pub mount_point: String,
+    pub file_system: String,
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+    pub percent: f64
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Battery {
+    pub percent: Option<u8>,
+    pub status: Option<String>,
+    pub health_percent: Option<u64>
+}
+
+/// Size of the terminal's palette: 8, 16 or 256 colors
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub enum Palette {
+    Ansi8,
+    #[default]
+    Ansi16,
+    Ansi256
+}
+
+impl Palette {
+    pub fn colors(self) -> u16 {
+        match self {
+            Palette::Ansi8 => 8,
+            Palette::Ansi16 => 16,
+            Palette::Ansi256 => 256
+        }
+    }
+}
+
+impl TryFrom<u16> for Palette {
+    type Error = String;
+
+    fn try_from(colors: u16) -> Result<Palette, String> {
+        match colors {
+            8 => Ok(Palette::Ansi8),
+            16 => Ok(Palette::Ansi16),
+            256 => Ok(Palette::Ansi256),
+            colors => Err(format!("the palette has 8, 16 or 256 colors, not {}", colors))
+        }
+    }
+}
+
+impl From<Palette> for u16 {
+    fn from(palette: Palette) -> u16 {
+        palette.colors()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Weather {
+    pub temperature_fahrenheit: f64,
+    pub description: String,
+    pub location: String
+}
+
+// Percentage of used out of total, or 0 if there is no total
+pub(crate) fn percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 * 100.0 / total as f64
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+
+// Format templates //
+
+/// A value that can be substituted into a template
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Int(u64),
+    /// A number and the precision it is printed with when the placeholder does not give one
+    Float(f64, usize)
+}
+
+impl Value {
+    // Format the value, with the precision given by the placeholder if any
+    fn format(&self, precision: Option<usize>) -> String {
+        match (self, precision) {
+            (Value::Text(text), _) => text.clone(),
+            (Value::Int(int), None) => int.to_string(),
+            (Value::Int(int), Some(precision)) => format!("{:.*}", precision, *int as f64),
+            (Value::Float(float, default), precision) => format!("{:.*}", precision.unwrap_or(*default), float)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError(String);
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Literal(String),
+    Placeholder { name: String, precision: Option<usize> }
+}
+
+/// A string with `{name}` placeholders, where `{name:.2}` sets the precision of numbers and
+/// `{{` and `}}` are literal braces
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    pieces: Vec<Piece>
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Template, TemplateError> {
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err(TemplateError(format!("unmatched `}}` in \"{}\", use `}}}}` for a literal brace", template))),
+                '{' => {
+                    // Read up to the closing brace
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => return Err(TemplateError(format!("unclosed `{{` in \"{}\", use `{{{{` for a literal brace", template)))
+                        }
+                    }
+
+                    let (name, precision) = match placeholder.split_once(':') {
+                        Some((name, spec)) => match spec.strip_prefix('.').and_then(|digits| digits.parse().ok()) {
+                            Some(precision) => (name, Some(precision)),
+                            None => return Err(TemplateError(format!("invalid format `{}` for `{{{}}}`, expected a precision such as `.2`", spec, name)))
+                        },
+                        None => (placeholder.as_str(), None)
+                    };
+                    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                        return Err(TemplateError(format!("invalid placeholder `{{{}}}`", placeholder)));
+                    }
+
+                    if !literal.is_empty() {
+                        pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                    }
+                    pieces.push(Piece::Placeholder { name: name.to_string(), precision });
+                }
+                c => literal.push(c)
+            }
+        }
+
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(literal));
+        }
+        Ok(Template { pieces })
+    }
+
+    /// Names of the placeholders used in the template
+    pub fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.pieces.iter().filter_map(|piece| match piece {
+            Piece::Placeholder { name, .. } => Some(name.as_str()),
+            Piece::Literal(_) => None
+        })
+    }
+
+    /// Substitute the values into the template, where placeholders without a value become `Unknown`
+    pub fn render(&self, values: &[(&str, Value)]) -> String {
+        self.pieces.iter().map(|piece| match piece {
+            Piece::Literal(literal) => literal.clone(),
+            Piece::Placeholder { name, precision } => match values.iter().find(|(key, _)| key == name) {
+                Some((_, value)) => value.format(*precision),
+                None => "Unknown".to_string()
+            }
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, values: &[(&str, Value)]) -> String {
+        Template::parse(template).unwrap().render(values)
+    }
+
+    #[test]
+    fn escaped_braces() {
+        assert_eq!(render("{{{name}}} {{}}", &[("name", Value::Text("x".to_string()))]), "{x} {}");
+        assert_eq!(Template::parse("{{name}}").unwrap().placeholders().count(), 0);
+    }
+
+    #[test]
+    fn precision() {
+        let values = [("ghz", Value::Float(3.4567, 1)), ("cores", Value::Int(8))];
+        assert_eq!(render("{ghz}", &values), "3.5");
+        assert_eq!(render("{ghz:.3}", &values), "3.457");
+        assert_eq!(render("{ghz:.0}", &values), "3");
+        assert_eq!(render("{cores} {cores:.2}", &values), "8 8.00");
+        assert!(Template::parse("{ghz:2}").is_err());
+        assert!(Template::parse("{ghz:.x}").is_err());
+    }
+
+    #[test]
+    fn missing_values() {
+        assert_eq!(render("{label}: {value}", &[("label", Value::Text("CPU".to_string()))]), "CPU: Unknown");
+    }
+
+    #[test]
+    fn unmatched_braces() {
+        assert!(Template::parse("{name").unwrap_err().to_string().starts_with("unclosed `{`"));
+        assert!(Template::parse("text {").is_err());
+        assert!(Template::parse("name}").unwrap_err().to_string().starts_with("unmatched `}`"));
+        assert!(Template::parse("{}").is_err());
+        assert!(Template::parse("{a b}").is_err());
+    }
+}